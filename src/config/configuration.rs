@@ -1,7 +1,14 @@
+use crate::core::normalizer::Normalization;
+
 // 配置管理类接口
 pub trait Configuration {
     fn get_main_dictionary(&self) -> String;
     fn get_quantifier_dictionary(&self) -> String;
     fn get_ext_dictionaries(&self) -> Vec<String>;
     fn get_ext_stop_word_dictionaries(&self) -> Vec<String>;
+    fn get_english_stop_word_dictionary(&self) -> Option<String>;
+    fn get_hmm_model(&self) -> Option<String>;
+    fn get_normalization(&self) -> Normalization;
+    fn get_t2s_dictionaries(&self) -> Vec<String>;
+    fn get_pinyin_dictionaries(&self) -> Vec<String>;
 }
@@ -1,4 +1,5 @@
 use crate::config::configuration::Configuration;
+use crate::core::normalizer::Normalization;
 use serde;
 use serde::{Deserialize, Serialize};
 use serde_yaml;
@@ -17,6 +18,16 @@ pub struct DefaultConfig {
     stop_word_dict: String,
     ext_dicts: Vec<String>,
     ext_stop_word_dicts: Vec<String>,
+    #[serde(default)]
+    english_stop_word_dict: Option<String>,
+    #[serde(default)]
+    hmm_model: Option<String>,
+    #[serde(default)]
+    normalization: Normalization,
+    #[serde(default)]
+    t2s_dicts: Vec<String>,
+    #[serde(default)]
+    pinyin_dicts: Vec<String>,
 }
 
 impl DefaultConfig {
@@ -75,6 +86,38 @@ impl Configuration for DefaultConfig {
         dicts.extend(ext_stopwords);
         dicts
     }
+
+    fn get_english_stop_word_dictionary(&self) -> Option<String> {
+        self.english_stop_word_dict
+            .as_ref()
+            .map(|dict| root_path() + dict.as_str())
+    }
+
+    fn get_hmm_model(&self) -> Option<String> {
+        self.hmm_model
+            .as_ref()
+            .map(|model| root_path() + model.as_str())
+    }
+
+    fn get_normalization(&self) -> Normalization {
+        self.normalization.clone()
+    }
+
+    fn get_t2s_dictionaries(&self) -> Vec<String> {
+        let root_path = root_path();
+        self.t2s_dicts
+            .iter()
+            .map(|dict| root_path.clone() + dict)
+            .collect()
+    }
+
+    fn get_pinyin_dictionaries(&self) -> Vec<String> {
+        let root_path = root_path();
+        self.pinyin_dicts
+            .iter()
+            .map(|dict| root_path.clone() + dict)
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -90,5 +133,20 @@ mod test {
         log::info!("{}", config.get_quantifier_dictionary());
         log::info!("{:?}", config.get_ext_dictionaries());
         log::info!("{:?}", config.get_ext_stop_word_dictionaries());
+        log::info!("{:?}", config.get_english_stop_word_dictionary());
+        log::info!("{:?}", config.get_hmm_model());
+        log::info!("{:?}", config.get_normalization());
+        log::info!("{:?}", config.get_t2s_dictionaries());
+        log::info!("{:?}", config.get_pinyin_dictionaries());
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        let yaml = "main_dict: main.dic\nquantifier_dict: quantifier.dic\n\
+                    stop_word_dict: stopword.dic\next_dicts: []\next_stop_word_dicts: []\n";
+        let config: DefaultConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(None, config.get_hmm_model());
+        assert_eq!(None, config.get_english_stop_word_dictionary());
+        assert!(config.get_pinyin_dictionaries().is_empty());
     }
 }
@@ -1,4 +1,5 @@
 use unicode_blocks;
+use unicode_general_category::{get_general_category, GeneralCategory};
 
 #[derive(Debug)]
 pub enum CharType {
@@ -16,13 +17,30 @@ impl From<char> for CharType {
         } else if (input >= 'a' && input <= 'z') || (input >= 'A' && input <= 'Z') {
             return CharType::ENGLISH;
         } else {
+            // extension j of unicode 17, not in unicode_blocks yet
+            if ('\u{323B0}'..='\u{3347F}').contains(&input) {
+                return CharType::CHINESE;
+            }
             if let Some(ub) = unicode_blocks::find_unicode_block(input) {
                 if ub == unicode_blocks::CJK_UNIFIED_IDEOGRAPHS
                     || ub == unicode_blocks::CJK_COMPATIBILITY_IDEOGRAPHS
                     || ub == unicode_blocks::CJK_UNIFIED_IDEOGRAPHS_EXTENSION_A
+                    || ub == unicode_blocks::CJK_UNIFIED_IDEOGRAPHS_EXTENSION_B
+                    || ub == unicode_blocks::CJK_UNIFIED_IDEOGRAPHS_EXTENSION_C
+                    || ub == unicode_blocks::CJK_UNIFIED_IDEOGRAPHS_EXTENSION_D
+                    || ub == unicode_blocks::CJK_UNIFIED_IDEOGRAPHS_EXTENSION_E
+                    || ub == unicode_blocks::CJK_UNIFIED_IDEOGRAPHS_EXTENSION_F
+                    || ub == unicode_blocks::CJK_UNIFIED_IDEOGRAPHS_EXTENSION_G
+                    || ub == unicode_blocks::CJK_UNIFIED_IDEOGRAPHS_EXTENSION_H
+                    || ub == unicode_blocks::CJK_UNIFIED_IDEOGRAPHS_EXTENSION_I
+                    || ub == unicode_blocks::CJK_COMPATIBILITY_IDEOGRAPHS_SUPPLEMENT
+                    || ub == unicode_blocks::CJK_RADICALS_SUPPLEMENT
+                    || ub == unicode_blocks::KANGXI_RADICALS
                 {
                     return CharType::CHINESE;
                 } else if ub == unicode_blocks::HALFWIDTH_AND_FULLWIDTH_FORMS
+                    || ub == unicode_blocks::BOPOMOFO
+                    || ub == unicode_blocks::BOPOMOFO_EXTENDED
                     || ub == unicode_blocks::HANGUL_SYLLABLES
                     || ub == unicode_blocks::HANGUL_JAMO
                     || ub == unicode_blocks::HANGUL_COMPATIBILITY_JAMO
@@ -33,24 +51,101 @@ impl From<char> for CharType {
                     return CharType::OtherCjk;
                 }
             }
+            // letters of other alphabets, like é, ß, α or д, with their combining marks
+            if is_letter_or_mark(input) {
+                return CharType::ENGLISH;
+            }
         }
         return CharType::USELESS;
     }
 }
 
-// full char -> half char && lowercase
+// cased letters of alphabets, not the letters of other scripts like thai or
+// devanagari, and the combining diacritical marks, not enclosing marks like
+// the keycap U+20E3
+fn is_letter_or_mark(input: char) -> bool {
+    match get_general_category(input) {
+        GeneralCategory::UppercaseLetter
+        | GeneralCategory::LowercaseLetter
+        | GeneralCategory::TitlecaseLetter => true,
+        GeneralCategory::NonspacingMark => is_combining_diacritic(input),
+        _ => false,
+    }
+}
+
+/// combining diacritical marks, not the marks of other scripts like the
+/// japanese voiced sound marks
+pub fn is_combining_diacritic(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+// fullwidth forms of the halfwidth katakana U+FF61..U+FF9F, the voiced sound
+// marks become combining marks so every char maps to exactly one char, the
+// `Width` normalization composes them with the kana before
+const HALFWIDTH_KATAKANA: [char; 63] = [
+    '。', '「', '」', '、', '・', 'ヲ', 'ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ャ', 'ュ', 'ョ', 'ッ', 'ー',
+    'ア', 'イ', 'ウ', 'エ', 'オ', 'カ', 'キ', 'ク', 'ケ', 'コ', 'サ', 'シ', 'ス', 'セ', 'ソ', 'タ',
+    'チ', 'ツ', 'テ', 'ト', 'ナ', 'ニ', 'ヌ', 'ネ', 'ノ', 'ハ', 'ヒ', 'フ', 'ヘ', 'ホ', 'マ', 'ミ',
+    'ム', 'メ', 'モ', 'ヤ', 'ユ', 'ヨ', 'ラ', 'リ', 'ル', 'レ', 'ロ', 'ワ', 'ン', '\u{3099}',
+    '\u{309A}',
+];
+
+// full char -> half char && case fold
 pub fn regularize(input: char) -> char {
+    case_fold(fold_width(input))
+}
+
+// full char -> half char, halfwidth katakana -> fullwidth
+pub fn fold_width(input: char) -> char {
     let mut input_code = u32::from(input);
     if input_code == 12288 {
         input_code -= 12256; // whitespace
     } else if input_code >= 65281 && input_code <= 65374 {
         input_code -= 65248; // full char
-    } else if input_code >= 'A' as u32 && input_code <= 'Z' as u32 {
-        input_code += 32; // lowercase
+    } else if (0xFF61..=0xFF9F).contains(&input_code) {
+        return HALFWIDTH_KATAKANA[(input_code - 0xFF61) as usize]; // halfwidth katakana
     }
     char::from_u32(input_code).unwrap()
 }
 
+// simple case folding, one char to one char so offsets are kept
+pub fn case_fold(input: char) -> char {
+    if input.is_ascii() {
+        return input.to_ascii_lowercase();
+    }
+    // folded further than lowercase
+    match input {
+        'ς' => return 'σ',
+        'ſ' => return 's',
+        'µ' => return 'μ',
+        'ϐ' => return 'β',
+        'ϑ' => return 'θ',
+        'ϕ' => return 'φ',
+        'ϖ' => return 'π',
+        'ϰ' => return 'κ',
+        'ϱ' => return 'ρ',
+        'ϵ' => return 'ε',
+        'ẛ' => return 'ṡ',
+        // the ypogegrammeni and the prosgegrammeni
+        '\u{0345}' | '\u{1FBE}' => return 'ι',
+        _ => {}
+    }
+    let mut lower = input.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(c), None) => c,
+        // like İ, which lowercases to two chars
+        _ => input,
+    }
+}
+
+#[allow(dead_code)]
 pub fn regularize_str(input: &str) -> String {
     let mut regular_str = String::from("");
     for c in input.chars() {
@@ -69,6 +164,39 @@ pub fn regular_char_enum(input: &str) -> Vec<(char, CharType)> {
     pair
 }
 
+pub fn is_punctuation(input: char) -> bool {
+    matches!(
+        get_general_category(input),
+        GeneralCategory::ConnectorPunctuation
+            | GeneralCategory::DashPunctuation
+            | GeneralCategory::OpenPunctuation
+            | GeneralCategory::ClosePunctuation
+            | GeneralCategory::InitialPunctuation
+            | GeneralCategory::FinalPunctuation
+            | GeneralCategory::OtherPunctuation
+    )
+}
+
+pub fn is_symbol(input: char) -> bool {
+    matches!(
+        get_general_category(input),
+        GeneralCategory::MathSymbol
+            | GeneralCategory::CurrencySymbol
+            | GeneralCategory::ModifierSymbol
+            | GeneralCategory::OtherSymbol
+    )
+}
+
+// pictographs, dingbats, flags, or anything joined or presented as an emoji
+pub fn is_emoji(grapheme: &str) -> bool {
+    grapheme.chars().any(|c| {
+        matches!(
+            u32::from(c),
+            0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x200D | 0xFE0F
+        )
+    })
+}
+
 pub fn utf8_slice(s: &str, begin: usize, end: usize) -> &str {
     if end < begin {
         return "";
@@ -100,3 +228,60 @@ pub fn utf8_till(s: &str, end: usize) -> &str {
 pub fn utf8_len(s: &str) -> usize {
     s.chars().count()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_char_type() {
+        // extension b, c, g, compatibility supplement and radicals
+        for c in ['𠀀', '𪜀', '𰀀', '丽', '⺈', '⼀', '𡃁'] {
+            assert!(matches!(CharType::from(c), CharType::CHINESE), "{}", c);
+        }
+        // extension h, i and j
+        for c in [
+            '\u{31350}',
+            '\u{323AF}',
+            '\u{2EBF0}',
+            '\u{2EE5D}',
+            '\u{323B0}',
+        ] {
+            assert!(matches!(CharType::from(c), CharType::CHINESE), "{:?}", c);
+        }
+        for c in ['ㄅ', 'ㆠ', 'あ', '한'] {
+            assert!(matches!(CharType::from(c), CharType::OtherCjk), "{}", c);
+        }
+        for c in ['é', 'ß', 'Ω', 'д', '\u{301}'] {
+            assert!(matches!(CharType::from(c), CharType::ENGLISH), "{}", c);
+        }
+        // thai, devanagari, kana supplement, the keycap and variation selector
+        for c in ['ก', 'क', '\u{1B001}', '\u{20E3}', '\u{FE0F}', '々'] {
+            assert!(matches!(CharType::from(c), CharType::USELESS), "{}", c);
+        }
+    }
+
+    #[test]
+    fn test_case_fold() {
+        assert_eq!('ι', case_fold('\u{0345}'));
+        assert_eq!('ι', case_fold('\u{1FBE}'));
+        assert_eq!('σ', case_fold('ς'));
+        assert_eq!('ä', case_fold('Ä'));
+    }
+
+    #[test]
+    fn test_supplementary_chars() {
+        let input = "Ａ𠀀𡃁Ｂ";
+        assert_eq!("a𠀀𡃁b", regularize_str(input));
+        assert_eq!(4, utf8_len(input));
+        assert_eq!("𠀀𡃁", utf8_slice(input, 1, 3));
+        assert_eq!("𡃁Ｂ", utf8_from(input, 2));
+    }
+
+    #[test]
+    fn test_halfwidth_katakana() {
+        assert_eq!("コンヒ\u{309A}ュータ", regularize_str("ｺﾝﾋﾟｭｰﾀ"));
+        assert_eq!("カ\u{3099}ソリン", regularize_str("ｶﾞｿﾘﾝ"));
+        assert_eq!("「テスト」・", regularize_str("｢ﾃｽﾄ｣･"));
+    }
+}
@@ -1,9 +1,11 @@
 use crate::core::char_util::utf8_len;
+use crate::core::char_util::utf8_slice;
 use crate::core::char_util::CharType;
 use crate::core::lexeme::{Lexeme, LexemeType};
 use crate::core::ordered_linked_list::OrderedLinkedList;
 use crate::core::segmentor::Segmenter;
 use crate::dict::dictionary::GLOBAL_DICT;
+use std::ops::Range;
 
 const SEGMENTER_NAME: &str = "CJK_SEGMENTER";
 
@@ -15,34 +17,39 @@ impl Segmenter for CJKSegmenter {
         &mut self,
         input: &str,
         cursor: usize,
-        curr_char_type: &CharType,
+        _curr_char_type: &CharType,
         origin_lexemes: &mut OrderedLinkedList<Lexeme>,
     ) {
-        match curr_char_type {
-            CharType::USELESS => {}
-            _ => {
-                let char_count = utf8_len(input);
-                let lock_guard = {cfg_if::cfg_if! {
-                    if #[cfg(feature="use-parking-lot")] {GLOBAL_DICT.read()}
-                    else /*if #[cfg(feature="use-std-sync")]*/ {
-                        match GLOBAL_DICT.read() {
-                            Err(_err) => return,
-                            Ok(lck) => lck
-                        }
-                    }
-                }};
-                let hits = lock_guard.match_in_main_dict_with_offset(
-                    input,
-                    cursor,
-                    char_count - cursor
-                );
-                for hit in hits.iter() {
-                    if hit.is_match() {
-                        let new_lexeme = Lexeme::new(hit.pos(), LexemeType::CNWORD);
-                        origin_lexemes.insert(new_lexeme);
-                    }
+        // a useless char may also start a dictionary word, like the "." of
+        // ".net", the hits are empty if no word does
+        let char_count = utf8_len(input);
+        let lock_guard = {cfg_if::cfg_if! {
+            if #[cfg(feature="use-parking-lot")] {GLOBAL_DICT.read()}
+            else /*if #[cfg(feature="use-std-sync")]*/ {
+                match GLOBAL_DICT.read() {
+                    Err(_err) => return,
+                    Ok(lck) => lck
                 }
             }
+        }};
+        let hits = lock_guard.match_in_main_dict_with_offset(
+            input,
+            cursor,
+            char_count - cursor
+        );
+        for hit in hits.iter() {
+            if hit.is_match() {
+                let text = utf8_slice(input, hit.pos().start, hit.pos().end);
+                let lexeme_type = if !text.contains(char::is_whitespace) {
+                    LexemeType::CNWORD
+                } else if is_whole_words(input, hit.pos()) {
+                    LexemeType::PHRASE
+                } else {
+                    continue;
+                };
+                let new_lexeme = Lexeme::new(hit.pos(), lexeme_type);
+                origin_lexemes.insert(new_lexeme);
+            }
         }
     }
 
@@ -50,3 +57,13 @@ impl Segmenter for CJKSegmenter {
         return SEGMENTER_NAME;
     }
 }
+
+// a phrase does not start or end inside a word, "new york" is not matched
+// in "renew yorkshire"
+fn is_whole_words(input: &str, pos: Range<usize>) -> bool {
+    let chars: Vec<char> = input.chars().collect();
+    let is_letter = |c: char| matches!(CharType::from(c), CharType::ENGLISH | CharType::ARABIC);
+    let joined = |a: usize, b: usize| is_letter(chars[a]) && is_letter(chars[b]);
+    (pos.start == 0 || !joined(pos.start - 1, pos.start))
+        && (pos.end == chars.len() || !joined(pos.end - 1, pos.end))
+}
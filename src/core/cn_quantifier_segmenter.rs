@@ -1,6 +1,7 @@
 use crate::core::char_util::utf8_len;
 use crate::core::char_util::CharType;
 use crate::core::lexeme::{Lexeme, LexemeType};
+use crate::core::number_util::{digit_value, parse_decimal, parse_number};
 use crate::core::ordered_linked_list::OrderedLinkedList;
 use crate::core::segmentor::Segmenter;
 use crate::dict::dictionary::GLOBAL_DICT;
@@ -8,11 +9,46 @@ use std::collections::HashSet;
 
 const SEGMENTER_NAME: &str = "QUAN_SEGMENTER";
 
+// longest amount looked ahead, like "百分之一百二十三点四五"
+const MAX_LOOKAHEAD: usize = 32;
+
+const CURRENCY_SYMBOLS: [(char, &str); 5] = [
+    ('$', "USD"),
+    ('¥', "CNY"),
+    ('￥', "CNY"),
+    ('€', "EUR"),
+    ('£', "GBP"),
+];
+
+// longer words first
+const CURRENCY_WORDS: [(&str, &str); 9] = [
+    ("美元", "USD"),
+    ("美金", "USD"),
+    ("欧元", "EUR"),
+    ("日元", "JPY"),
+    ("英镑", "GBP"),
+    ("港元", "HKD"),
+    ("港币", "HKD"),
+    ("块钱", "CNY"),
+    ("元", "CNY"),
+];
+
+// currency words which are also parts of other words
+const BARE_CURRENCY_WORDS: [&str; 2] = ["块钱", "元"];
+
+const NUMBER_UNITS: [char; 5] = ['十', '百', '千', '万', '亿'];
+
+const RANGE_CONNECTORS: [char; 4] = ['-', '~', '到', '至'];
+
 #[derive(Debug)]
 pub struct CnQuantifierSegmenter {
     start: Option<usize>,
     end: Option<usize>,
     chn_number_chars: HashSet<char>,
+    // end of the last recognized percentage or amount of money
+    amount_end: usize,
+    // end of the last recognized ordinal or range
+    range_end: usize,
 }
 
 impl Segmenter for CnQuantifierSegmenter {
@@ -23,6 +59,8 @@ impl Segmenter for CnQuantifierSegmenter {
         curr_char_type: &CharType,
         origin_lexemes: &mut OrderedLinkedList<Lexeme>,
     ) {
+        self.process_amount(input, cursor, origin_lexemes);
+        self.process_range(input, cursor, origin_lexemes);
         self.process_cnumber(input, cursor, curr_char_type, origin_lexemes);
         self.process_count(input, cursor, curr_char_type, origin_lexemes);
     }
@@ -39,8 +77,10 @@ impl Default for CnQuantifierSegmenter {
             chn_number_chars: HashSet::from([
                 '一', '二', '两', '三', '四', '五', '六', '七', '八', '九', '十', '零', '壹', '贰',
                 '叁', '肆', '伍', '陆', '柒', '捌', '玖', '拾', '百', '千', '万', '亿', '拾', '佰',
-                '仟', '萬', '億', '兆', '卅', '廿',
+                '仟', '萬', '億', '兆', '卅', '廿', '卌', '〇',
             ]),
+            amount_end: 0,
+            range_end: 0,
         }
     }
 }
@@ -64,8 +104,11 @@ impl CnQuantifierSegmenter {
                 _ => {}
             }
         } else {
+            // 〇 is not in a CJK ideographs block
             match curr_char_type {
-                CharType::CHINESE if self.chn_number_chars.contains(&curr_char) => {
+                CharType::CHINESE | CharType::USELESS
+                    if self.chn_number_chars.contains(&curr_char) =>
+                {
                     self.end = Some(cursor);
                 }
                 _ => {
@@ -87,6 +130,271 @@ impl CnQuantifierSegmenter {
         });
     }
 
+    /// percentages and amounts of money, like "50%", "百分之五十", "$12" or
+    /// "3.5亿元", the value is "50%" or "CNY 350000000"
+    fn process_amount(
+        &mut self,
+        input: &str,
+        cursor: usize,
+        origin_lexemes: &mut OrderedLinkedList<Lexeme>,
+    ) {
+        if cursor < self.amount_end {
+            return;
+        }
+        let Some(chars) = self.lookahead(input, cursor) else {
+            return;
+        };
+        if let Some((len, lexeme_type, value)) = self.parse_amount(&chars) {
+            let mut new_lexeme = Lexeme::new(cursor..cursor + len, lexeme_type);
+            new_lexeme.set_value(Some(value));
+            origin_lexemes.insert(new_lexeme);
+            self.amount_end = cursor + len;
+        }
+    }
+
+    /// ordinals and approximate numbers or ranges together with the
+    /// quantifier following them, like "第三名", "3-5个", "十几岁" or "二三十",
+    /// the value is "3" or "3-5"
+    fn process_range(
+        &mut self,
+        input: &str,
+        cursor: usize,
+        origin_lexemes: &mut OrderedLinkedList<Lexeme>,
+    ) {
+        if cursor < self.range_end {
+            return;
+        }
+        let Some(chars) = self.lookahead(input, cursor) else {
+            return;
+        };
+        let Some((len, lexeme_type, value, need_count)) = self.parse_range(&chars) else {
+            return;
+        };
+        let mut end = cursor + len;
+        match self.match_count(input, end) {
+            Some(count_end) => end = count_end,
+            // "3-5" alone may be a score or a part number
+            None if need_count => return,
+            None => {}
+        }
+        let mut new_lexeme = Lexeme::new(cursor..end, lexeme_type);
+        new_lexeme.set_value(Some(value));
+        origin_lexemes.insert(new_lexeme);
+        self.range_end = end;
+    }
+
+    // chars from the cursor, none if the cursor is inside a number
+    fn lookahead(&self, input: &str, cursor: usize) -> Option<Vec<char>> {
+        let mut chars = Vec::with_capacity(MAX_LOOKAHEAD);
+        for (i, c) in input.chars().enumerate().skip(cursor.saturating_sub(1)) {
+            if i < cursor {
+                if self.is_number_char(c) {
+                    return None;
+                }
+            } else if chars.len() < MAX_LOOKAHEAD {
+                chars.push(c);
+            } else {
+                break;
+            }
+        }
+        Some(chars)
+    }
+
+    // whether the chars are a word of the main dict or the start of one
+    fn starts_word(&self, chars: &[char]) -> bool {
+        let word: String = chars.iter().collect();
+        let lock_guard = {cfg_if::cfg_if! {
+            if #[cfg(feature="use-parking-lot")] {GLOBAL_DICT.read()}
+            else /*if #[cfg(feature="use-std-sync")]*/ {
+                match GLOBAL_DICT.read() {
+                    Err(_err) => return false,
+                    Ok(lck) => lck
+                }
+            }
+        }};
+        let hits = lock_guard.match_in_main_dict(&word);
+        hits.iter().any(|hit| hit.pos().end == chars.len())
+    }
+
+    // end of the longest quantifier starting at begin
+    fn match_count(&self, input: &str, begin: usize) -> Option<usize> {
+        let char_count = utf8_len(input);
+        if begin >= char_count {
+            return None;
+        }
+        let lock_guard = {cfg_if::cfg_if! {
+            if #[cfg(feature="use-parking-lot")] {GLOBAL_DICT.read()}
+            else /*if #[cfg(feature="use-std-sync")]*/ {
+                match GLOBAL_DICT.read() {
+                    Err(_err) => return None,
+                    Ok(lck) => lck
+                }
+            }
+        }};
+        let hits = lock_guard.match_in_quantifier_dict(input, begin, char_count - begin);
+        hits.iter()
+            .filter(|hit| hit.is_match())
+            .map(|hit| hit.pos().end)
+            .max()
+    }
+
+    fn parse_range(&self, chars: &[char]) -> Option<(usize, LexemeType, String, bool)> {
+        // 第三, 第10
+        if chars.first() == Some(&'第') {
+            let len = self.integer_len(&chars[1..]);
+            let value = parse_number(&chars[1..1 + len].iter().collect::<String>())?;
+            return Some((1 + len, LexemeType::ORDINAL, value.to_string(), false));
+        }
+        // 几十, 几百
+        if chars.first() == Some(&'几') {
+            let unit = parse_number(&chars.get(1)?.to_string())?;
+            if !NUMBER_UNITS.contains(&chars[1]) {
+                return None;
+            }
+            let value = format!("{}-{}", 2 * unit, 9 * unit);
+            return Some((2, LexemeType::RANGE, value, false));
+        }
+        let len = self.integer_len(chars);
+        if len == 0 {
+            return None;
+        }
+        let text: String = chars[..len].iter().collect();
+        // 3-5, 三到五
+        if let Some(connector) = chars.get(len).filter(|c| RANGE_CONNECTORS.contains(c)) {
+            let to_len = self.integer_len(&chars[len + 1..]);
+            let to_text: String = chars[len + 1..len + 1 + to_len].iter().collect();
+            let (from, to) = (parse_number(&text)?, parse_number(&to_text)?);
+            if from >= to {
+                return None;
+            }
+            let need_count = *connector == '-' || *connector == '~';
+            let value = format!("{}-{}", from, to);
+            return Some((len + 1 + to_len, LexemeType::RANGE, value, need_count));
+        }
+        // 十几, 二十几万
+        if chars[len - 1] == '十' && chars.get(len) == Some(&'几') {
+            let base = parse_number(&text)?;
+            let mut end = len + 1;
+            let mut unit = 1;
+            if let Some(big_unit) = chars.get(end).filter(|c| **c == '万' || **c == '亿') {
+                unit = parse_number(&big_unit.to_string())?;
+                end += 1;
+            }
+            let value = format!("{}-{}", (base + 1) * unit, (base + 9) * unit);
+            return Some((end, LexemeType::RANGE, value, false));
+        }
+        // 两三, 二三十, 七八百
+        if len >= 2 && !NUMBER_UNITS.contains(&chars[0]) && !NUMBER_UNITS.contains(&chars[1]) {
+            let from = digit_value(chars[0]).filter(|d| *d > 0)?;
+            let to = digit_value(chars[1])?;
+            if to != from + 1 || chars[0].is_ascii_digit() {
+                return None;
+            }
+            let mut unit = 1;
+            if len > 2 {
+                let units: String = chars[2..len].iter().collect();
+                if !units.chars().all(|c| NUMBER_UNITS.contains(&c)) {
+                    return None;
+                }
+                unit = parse_number(&units)?;
+            }
+            let value = format!("{}-{}", from * unit, to * unit);
+            return Some((len, LexemeType::RANGE, value, len == 2));
+        }
+        None
+    }
+
+    // length of the integer at the start, arabic digits or chinese numerals
+    fn integer_len(&self, chars: &[char]) -> usize {
+        match chars.first() {
+            Some(c) if c.is_ascii_digit() => {
+                chars.iter().take_while(|c| c.is_ascii_digit()).count()
+            }
+            Some(_) => chars
+                .iter()
+                .take_while(|c| self.chn_number_chars.contains(c))
+                .count(),
+            None => 0,
+        }
+    }
+
+    fn parse_amount(&self, chars: &[char]) -> Option<(usize, LexemeType, String)> {
+        if chars.iter().take(3).eq(['百', '分', '之'].iter()) {
+            let len = self.number_len(&chars[3..]);
+            let value = parse_decimal(&chars[3..3 + len].iter().collect::<String>())?;
+            return Some((3 + len, LexemeType::PERCENT, format!("{}%", value)));
+        }
+        let symbol = chars.first().and_then(|c| {
+            CURRENCY_SYMBOLS
+                .iter()
+                .find(|(symbol, _)| symbol == c)
+                .map(|(_, currency)| *currency)
+        });
+        let start = if symbol.is_some() { 1 } else { 0 };
+        let number_len = self.number_len(&chars[start..]);
+        if number_len == 0 {
+            return None;
+        }
+        let end = start + number_len;
+        let value = parse_decimal(&chars[start..end].iter().collect::<String>())?;
+        if symbol.is_none() && chars.get(end) == Some(&'%') {
+            return Some((end + 1, LexemeType::PERCENT, format!("{}%", value)));
+        }
+        let arabic = chars[start].is_ascii_digit();
+        for (word, currency) in CURRENCY_WORDS.iter() {
+            let word_len = word.chars().count();
+            if chars[end..].iter().copied().take(word_len).eq(word.chars()) {
+                // "一元二次方程" and "3元素" are not money, a bare "元" after
+                // chinese numerals is only taken at the end of a word, after
+                // arabic digits unless it starts a word
+                let followed_by_word = chars
+                    .get(end + word_len)
+                    .is_some_and(|c| *c != '的' && matches!(CharType::from(*c), CharType::CHINESE));
+                if BARE_CURRENCY_WORDS.contains(word)
+                    && followed_by_word
+                    && (!arabic || self.starts_word(&chars[end..end + word_len + 1]))
+                {
+                    return None;
+                }
+                let value = format!("{} {}", currency, value);
+                return Some((end + word_len, LexemeType::MONEY, value));
+            }
+        }
+        symbol.map(|currency| (end, LexemeType::MONEY, format!("{} {}", currency, value)))
+    }
+
+    // length of the number at the start, arabic digits with a fraction and
+    // units, like "3.5亿", or chinese numerals like "三点五"
+    fn number_len(&self, chars: &[char]) -> usize {
+        let mut len = 0;
+        if chars.first().is_some_and(|c| c.is_ascii_digit()) {
+            len = chars
+                .iter()
+                .take_while(|c| c.is_ascii_digit() || **c == '.' || **c == ',')
+                .count();
+            while len > 0 && !chars[len - 1].is_ascii_digit() {
+                len -= 1;
+            }
+            len += chars[len..]
+                .iter()
+                .take_while(|c| NUMBER_UNITS.contains(c))
+                .count();
+        } else if chars
+            .first()
+            .is_some_and(|c| self.chn_number_chars.contains(c))
+        {
+            len = chars
+                .iter()
+                .take_while(|c| self.chn_number_chars.contains(c) || **c == '点')
+                .count();
+        }
+        len
+    }
+
+    fn is_number_char(&self, c: char) -> bool {
+        c.is_ascii_digit() || self.chn_number_chars.contains(&c)
+    }
+
     fn process_count(
         &mut self,
         input: &str,
@@ -138,7 +446,7 @@ impl CnQuantifierSegmenter {
         let last = origin_lexemes.peek_back();
         let mut need_scan = false;
         last.map(|lexeme| match lexeme.lexeme_type() {
-            LexemeType::ARABIC | LexemeType::CNUM => {
+            LexemeType::ARABIC | LexemeType::DECIMAL | LexemeType::CNUM => {
                 if lexeme.end_pos() == cursor {
                     need_scan = true;
                 }
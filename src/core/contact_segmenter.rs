@@ -0,0 +1,237 @@
+use crate::core::char_util::CharType;
+use crate::core::lexeme::{Lexeme, LexemeType};
+use crate::core::ordered_linked_list::OrderedLinkedList;
+use crate::core::segmentor::Segmenter;
+
+const SEGMENTER_NAME: &str = "CONTACT_SEGMENTER";
+
+const URL_PREFIXES: [&str; 4] = ["http://", "https://", "ftp://", "www."];
+
+const URL_CHARS: &str = "-._~:/?#[]@!$&'()*+,;=%";
+
+// not part of an url at its end, like the "." closing a sentence
+const URL_TRAILING_CHARS: &str = ".,;:!?)'";
+
+const EMAIL_USER_CHARS: &str = "._%+-";
+
+/// recognize emails, urls, ipv4 addresses and chinese mobile and landline
+/// phone numbers as EMAIL, URL, IP and PHONE lexemes
+#[derive(Default, Debug)]
+pub struct ContactSegmenter {
+    // end of the last recognized lexeme
+    end: usize,
+}
+
+impl Segmenter for ContactSegmenter {
+    fn analyze(
+        &mut self,
+        input: &str,
+        cursor: usize,
+        curr_char_type: &CharType,
+        origin_lexemes: &mut OrderedLinkedList<Lexeme>,
+    ) {
+        if cursor < self.end {
+            return;
+        }
+        match curr_char_type {
+            CharType::ARABIC | CharType::ENGLISH => {}
+            _ => return,
+        }
+        let mut chars = input.chars().skip(cursor.saturating_sub(1));
+        if cursor > 0 {
+            // only at the start of a word
+            match chars.next() {
+                Some(c) if c.is_ascii_alphanumeric() || EMAIL_USER_CHARS.contains(c) => return,
+                _ => {}
+            }
+        }
+        let chars: Vec<char> = chars.take_while(|c| c.is_ascii_graphic()).collect();
+        if chars.is_empty() {
+            return;
+        }
+        let recognized = parse_url(&chars)
+            .map(|len| (len, LexemeType::URL))
+            .or_else(|| parse_email(&chars).map(|len| (len, LexemeType::EMAIL)))
+            .or_else(|| parse_ip(&chars).map(|len| (len, LexemeType::IP)))
+            .or_else(|| parse_phone(&chars).map(|len| (len, LexemeType::PHONE)));
+        if let Some((len, lexeme_type)) = recognized {
+            origin_lexemes.insert(Lexeme::new(cursor..cursor + len, lexeme_type));
+            self.end = cursor + len;
+        }
+    }
+
+    fn name(&self) -> &str {
+        SEGMENTER_NAME
+    }
+}
+
+fn digits(chars: &[char], i: usize) -> usize {
+    chars.get(i..).map_or(0, |rest| {
+        rest.iter().take_while(|c| c.is_ascii_digit()).count()
+    })
+}
+
+fn followed_by_digit(chars: &[char], i: usize) -> bool {
+    chars.get(i).is_some_and(|c| c.is_ascii_digit())
+}
+
+// https://github.com/jobdeng/ik-rs, www.example.com/a?b=c
+fn parse_url(chars: &[char]) -> Option<usize> {
+    let prefix = URL_PREFIXES
+        .iter()
+        .find(|prefix| chars.iter().copied().take(prefix.len()).eq(prefix.chars()))?;
+    let mut len = chars
+        .iter()
+        .take_while(|c| c.is_ascii_alphanumeric() || URL_CHARS.contains(**c))
+        .count();
+    while len > prefix.len() && URL_TRAILING_CHARS.contains(chars[len - 1]) {
+        len -= 1;
+    }
+    if !chars.get(prefix.len())?.is_ascii_alphanumeric() {
+        return None;
+    }
+    Some(len)
+}
+
+// zhiyi.shen@gmail.com
+fn parse_email(chars: &[char]) -> Option<usize> {
+    let user_len = chars
+        .iter()
+        .take_while(|c| c.is_ascii_alphanumeric() || EMAIL_USER_CHARS.contains(**c))
+        .count();
+    if user_len == 0 || chars.get(user_len) != Some(&'@') {
+        return None;
+    }
+    let domain = &chars[user_len + 1..];
+    let mut domain_len = domain
+        .iter()
+        .take_while(|c| c.is_ascii_alphanumeric() || **c == '-' || **c == '.')
+        .count();
+    while domain_len > 0 && (domain[domain_len - 1] == '.' || domain[domain_len - 1] == '-') {
+        domain_len -= 1;
+    }
+    let labels: Vec<&[char]> = domain[..domain_len].split(|c| *c == '.').collect();
+    let tld = labels[labels.len() - 1];
+    if labels.len() < 2
+        || labels.iter().any(|label| label.is_empty())
+        || tld.len() < 2
+        || !tld.iter().all(|c| c.is_ascii_alphabetic())
+    {
+        return None;
+    }
+    Some(user_len + 1 + domain_len)
+}
+
+// 192.168.1.1
+fn parse_ip(chars: &[char]) -> Option<usize> {
+    let mut i = 0;
+    for octet in 0..4 {
+        if octet > 0 {
+            if chars.get(i) != Some(&'.') {
+                return None;
+            }
+            i += 1;
+        }
+        let len = digits(chars, i);
+        if len == 0 || len > 3 {
+            return None;
+        }
+        let value: u32 = chars[i..i + len].iter().collect::<String>().parse().ok()?;
+        if value > 255 {
+            return None;
+        }
+        i += len;
+    }
+    // not a version number like 1.2.3.4.5
+    if chars.get(i) == Some(&'.') && followed_by_digit(chars, i + 1) {
+        return None;
+    }
+    Some(i)
+}
+
+// 13812345678, 138-1234-5678, 010-12345678, 0571-8888888
+fn parse_phone(chars: &[char]) -> Option<usize> {
+    let head = digits(chars, 0);
+    let mobile_prefix = chars[0] == '1' && chars.get(1).is_some_and(|c| ('3'..='9').contains(c));
+    let len = if mobile_prefix && head == 11 {
+        11
+    } else if mobile_prefix
+        && head == 3
+        && chars.get(3) == Some(&'-')
+        && digits(chars, 4) == 4
+        && chars.get(8) == Some(&'-')
+        && digits(chars, 9) == 4
+    {
+        13
+    } else if chars[0] == '0' && (head == 3 || head == 4) && chars.get(head) == Some(&'-') {
+        let number = digits(chars, head + 1);
+        if number != 7 && number != 8 {
+            return None;
+        }
+        head + 1 + number
+    } else {
+        return None;
+    };
+    if followed_by_digit(chars, len) {
+        return None;
+    }
+    Some(len)
+}
+
+/// positions of the user and domain of an email, or the host of an url,
+/// relative to the lexeme text
+pub(crate) fn sub_token_ranges(lexeme_type: &LexemeType, text: &str) -> Vec<(usize, usize)> {
+    // the text of these lexemes is ascii, byte and char offsets are the same
+    match lexeme_type {
+        LexemeType::EMAIL => match text.find('@') {
+            Some(at) => vec![(0, at), (at + 1, text.len())],
+            None => Vec::new(),
+        },
+        LexemeType::URL => {
+            let host_start = text.find("://").map_or(0, |i| i + 3);
+            let host_end = text[host_start..]
+                .find(|c| c == '/' || c == ':' || c == '?' || c == '#')
+                .map_or(text.len(), |i| host_start + i);
+            vec![(host_start, host_end)]
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn recognize(text: &str) -> Option<(usize, &'static str)> {
+        let chars: Vec<char> = text.chars().collect();
+        parse_url(&chars)
+            .map(|len| (len, "URL"))
+            .or_else(|| parse_email(&chars).map(|len| (len, "EMAIL")))
+            .or_else(|| parse_ip(&chars).map(|len| (len, "IP")))
+            .or_else(|| parse_phone(&chars).map(|len| (len, "PHONE")))
+    }
+
+    #[test]
+    fn test_recognize_contact() {
+        assert_eq!(Some((20, "EMAIL")), recognize("zhiyi.shen@gmail.com"));
+        assert_eq!(Some((20, "EMAIL")), recognize("zhiyi.shen@gmail.com."));
+        assert_eq!(Some((21, "URL")), recognize("https://a.com/b?c=d#e."));
+        assert_eq!(Some((15, "URL")), recognize("www.example.com"));
+        assert_eq!(Some((11, "IP")), recognize("192.168.1.1"));
+        assert_eq!(Some((11, "PHONE")), recognize("13812345678"));
+        assert_eq!(Some((13, "PHONE")), recognize("138-1234-5678"));
+        assert_eq!(Some((12, "PHONE")), recognize("010-12345678"));
+        assert_eq!(None, recognize("1.2.3.4.5"));
+        assert_eq!(None, recognize("256.1.1.1"));
+        assert_eq!(None, recognize("a@b"));
+        assert_eq!(None, recognize("123456789012"));
+        assert_eq!(
+            vec![(0, 10), (11, 20)],
+            sub_token_ranges(&LexemeType::EMAIL, "zhiyi.shen@gmail.com")
+        );
+        assert_eq!(
+            vec![(8, 18)],
+            sub_token_ranges(&LexemeType::URL, "https://github.com/jobdeng")
+        );
+    }
+}
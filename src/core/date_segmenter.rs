@@ -0,0 +1,318 @@
+use crate::core::char_util::CharType;
+use crate::core::lexeme::{Lexeme, LexemeType};
+use crate::core::number_util::parse_number;
+use crate::core::ordered_linked_list::OrderedLinkedList;
+use crate::core::segmentor::Segmenter;
+
+const SEGMENTER_NAME: &str = "DATE_SEGMENTER";
+
+// longest date or time looked ahead, like "二〇二三年十二月三十一日"
+const MAX_LOOKAHEAD: usize = 24;
+
+const CN_DIGITS: [char; 16] = [
+    '〇', '零', '一', '二', '两', '三', '四', '五', '六', '七', '八', '九', '十', '廿', '卅', '卌',
+];
+
+const DATE_SEPARATORS: [char; 3] = ['-', '/', '.'];
+
+// period of the day and the hours to add to a 12-hour clock time
+const DAY_PERIODS: [(&str, u32); 9] = [
+    ("凌晨", 0),
+    ("早上", 0),
+    ("上午", 0),
+    ("中午", 12),
+    ("下午", 12),
+    ("傍晚", 12),
+    ("晚上", 12),
+    ("夜里", 12),
+    ("半夜", 12),
+];
+
+// periods of the night, their 12点 is midnight and their hours before 6 are
+// after midnight, like "半夜2点"
+const NIGHT_PERIODS: [&str; 4] = ["凌晨", "晚上", "夜里", "半夜"];
+
+/// recognize dates and times like "2023年10月1日", "十月一日", "2023-10-01",
+/// "下午3点半" and "15:30", the iso form is set as the lexeme value
+#[derive(Default, Debug)]
+pub struct DateSegmenter {
+    // end of the last recognized date or time
+    end: usize,
+}
+
+impl Segmenter for DateSegmenter {
+    fn analyze(
+        &mut self,
+        input: &str,
+        cursor: usize,
+        _curr_char_type: &CharType,
+        origin_lexemes: &mut OrderedLinkedList<Lexeme>,
+    ) {
+        if cursor < self.end {
+            return;
+        }
+        let mut prev_char = None;
+        let mut chars = Vec::with_capacity(MAX_LOOKAHEAD);
+        for (i, c) in input.chars().enumerate().skip(cursor.saturating_sub(1)) {
+            if i < cursor {
+                prev_char = Some(c);
+            } else if chars.len() < MAX_LOOKAHEAD {
+                chars.push(c);
+            } else {
+                break;
+            }
+        }
+        // not inside a number
+        if prev_char.is_some_and(|c| c.is_ascii_digit() || CN_DIGITS.contains(&c)) {
+            return;
+        }
+        let recognized = parse_date(&chars)
+            .map(|(len, value)| (len, value, LexemeType::DATE))
+            .or_else(|| parse_time(&chars).map(|(len, value)| (len, value, LexemeType::TIME)));
+        if let Some((len, value, lexeme_type)) = recognized {
+            let mut new_lexeme = Lexeme::new(cursor..cursor + len, lexeme_type);
+            new_lexeme.set_value(Some(value));
+            origin_lexemes.insert(new_lexeme);
+            self.end = cursor + len;
+        }
+    }
+
+    fn name(&self) -> &str {
+        SEGMENTER_NAME
+    }
+}
+
+// a run of arabic digits or of chinese digits at i, returns its value and length
+fn take_number(chars: &[char], i: usize) -> Option<(u32, usize)> {
+    let first = *chars.get(i)?;
+    let len = if first.is_ascii_digit() {
+        chars[i..].iter().take_while(|c| c.is_ascii_digit()).count()
+    } else {
+        chars[i..]
+            .iter()
+            .take_while(|c| CN_DIGITS.contains(c))
+            .count()
+    };
+    if len == 0 {
+        return None;
+    }
+    let text: String = chars[i..i + len].iter().collect();
+    let value = parse_number(&text)?;
+    u32::try_from(value).ok().map(|value| (value, len))
+}
+
+// number at i followed by one of the suffixes, returns the value and the
+// length including the suffix
+fn take_number_with(chars: &[char], i: usize, suffixes: &[char]) -> Option<(u32, usize)> {
+    let (value, len) = take_number(chars, i)?;
+    match chars.get(i + len) {
+        Some(c) if suffixes.contains(c) => Some((value, len + 1)),
+        _ => None,
+    }
+}
+
+// the days of the month, february has 29 days if the year is not known
+fn valid_date(year: Option<u32>, month: u32, day: Option<u32>) -> bool {
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => match year {
+            Some(year) if !((year % 4 == 0 && year % 100 != 0) || year % 400 == 0) => 28,
+            _ => 29,
+        },
+        _ => return false,
+    };
+    day.map_or(true, |day| (1..=days).contains(&day))
+}
+
+// 24:00 is the end of a day, no time is later
+fn valid_time(hour: u32, minute: u32, second: Option<u32>) -> bool {
+    let second = second.unwrap_or(0);
+    (hour < 24 || (hour == 24 && minute == 0 && second == 0)) && minute < 60 && second < 60
+}
+
+fn parse_date(chars: &[char]) -> Option<(usize, String)> {
+    parse_iso_date(chars).or_else(|| parse_cn_date(chars))
+}
+
+// 2023-10-01, 2023/10/1
+fn parse_iso_date(chars: &[char]) -> Option<(usize, String)> {
+    if chars.len() < 8 || !chars[..4].iter().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let separator = chars[4];
+    if !DATE_SEPARATORS.contains(&separator) {
+        return None;
+    }
+    let year: String = chars[..4].iter().collect();
+    let year_value = year.parse::<u32>().ok();
+    let (month, month_len) = take_number(chars, 5).filter(|(_, len)| *len <= 2)?;
+    let mut i = 5 + month_len;
+    if chars.get(i) != Some(&separator) || !chars.get(i + 1)?.is_ascii_digit() {
+        return None;
+    }
+    let (day, day_len) = take_number(chars, i + 1).filter(|(_, len)| *len <= 2)?;
+    i += 1 + day_len;
+    if !valid_date(year_value, month, Some(day)) {
+        return None;
+    }
+    Some((i, format!("{}-{:02}-{:02}", year, month, day)))
+}
+
+// 2023年10月1日, 二〇二三年十月, 十月一日, 10月1号
+fn parse_cn_date(chars: &[char]) -> Option<(usize, String)> {
+    let mut i = 0;
+    let mut year = None;
+    if let Some((value, len)) = take_number_with(chars, 0, &['年']) {
+        // only a full year, "3年" is a duration
+        if len == 5 {
+            year = Some(value);
+            i = len;
+        }
+    }
+    let (month, month_len) = take_number_with(chars, i, &['月'])?;
+    i += month_len;
+    let mut day = None;
+    if let Some((value, len)) = take_number_with(chars, i, &['日', '号']) {
+        day = Some(value);
+        i += len;
+    }
+    if !valid_date(year, month, day) {
+        return None;
+    }
+    match (year, day) {
+        (Some(year), Some(day)) => Some((i, format!("{:04}-{:02}-{:02}", year, month, day))),
+        (Some(year), None) => Some((i, format!("{:04}-{:02}", year, month))),
+        (None, Some(day)) => Some((i, format!("--{:02}-{:02}", month, day))),
+        (None, None) => None,
+    }
+}
+
+fn parse_time(chars: &[char]) -> Option<(usize, String)> {
+    parse_iso_time(chars).or_else(|| parse_cn_time(chars))
+}
+
+// 15:30, 9:05:10
+fn parse_iso_time(chars: &[char]) -> Option<(usize, String)> {
+    let (hour, hour_len) = take_number(chars, 0).filter(|(_, len)| *len <= 2)?;
+    if !chars[0].is_ascii_digit() {
+        return None;
+    }
+    let mut i = hour_len;
+    let mut parts = vec![hour];
+    while parts.len() < 3 && chars.get(i) == Some(&':') {
+        let (value, len) = take_number(chars, i + 1).filter(|(_, len)| *len == 2)?;
+        parts.push(value);
+        i += 1 + len;
+    }
+    if parts.len() < 2 || !valid_time(hour, parts[1], parts.get(2).copied()) {
+        return None;
+    }
+    let value = parts
+        .iter()
+        .map(|v| format!("{:02}", v))
+        .collect::<Vec<String>>()
+        .join(":");
+    Some((i, value))
+}
+
+// 下午3点半, 三点钟, 15点20分, 上午十点零五分三十秒
+fn parse_cn_time(chars: &[char]) -> Option<(usize, String)> {
+    let mut i = 0;
+    let mut period = None;
+    let mut night = false;
+    for (name, hours) in DAY_PERIODS.iter() {
+        if chars.len() > 2 && chars[..2].iter().copied().eq(name.chars()) {
+            period = Some(*hours);
+            night = NIGHT_PERIODS.contains(name);
+            i = 2;
+            break;
+        }
+    }
+    let (mut hour, hour_len) = take_number_with(chars, i, &['点', '时'])?;
+    i += hour_len;
+    let mut minute = None;
+    let mut second = None;
+    match chars.get(i) {
+        Some('钟') => {
+            minute = Some(0);
+            i += 1;
+        }
+        Some('半') => {
+            minute = Some(30);
+            i += 1;
+        }
+        Some('一') | Some('三') if chars.get(i + 1) == Some(&'刻') => {
+            minute = Some(if chars[i] == '一' { 15 } else { 45 });
+            i += 2;
+        }
+        _ => {
+            if let Some((value, len)) = take_number_with(chars, i, &['分']) {
+                minute = Some(value);
+                i += len;
+                if let Some((value, len)) = take_number_with(chars, i, &['秒']) {
+                    second = Some(value);
+                    i += len;
+                }
+            }
+        }
+    }
+    // "3点" alone is more likely a quantity
+    if period.is_none() && minute.is_none() {
+        return None;
+    }
+    match period {
+        Some(_) if night && hour == 12 => hour = 0,
+        Some(_) if night && hour < 6 => {}
+        Some(hours) if hour < 12 => hour += hours,
+        _ => {}
+    }
+    let minute = minute.unwrap_or(0);
+    if !valid_time(hour, minute, second) {
+        return None;
+    }
+    let value = match second {
+        Some(second) => format!("{:02}:{:02}:{:02}", hour, minute, second),
+        None => format!("{:02}:{:02}", hour, minute),
+    };
+    Some((i, value))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(text: &str) -> Option<(usize, String)> {
+        let chars: Vec<char> = text.chars().collect();
+        parse_date(&chars).or_else(|| parse_time(&chars))
+    }
+
+    #[test]
+    fn test_parse_date_time() {
+        assert_eq!(Some((10, "2023-10-01".to_string())), parse("2023年10月1日"));
+        assert_eq!(Some((4, "--10-01".to_string())), parse("十月一日"));
+        assert_eq!(Some((10, "2023-10-01".to_string())), parse("2023-10-01"));
+        assert_eq!(Some((7, "1949-10".to_string())), parse("一九四九年十月"));
+        assert_eq!(Some((5, "15:30".to_string())), parse("下午3点半"));
+        assert_eq!(Some((5, "15:30".to_string())), parse("15:30"));
+        assert_eq!(Some((8, "10:05:30".to_string())), parse("十点零五分三十秒"));
+        assert_eq!(None, parse("3点"));
+        assert_eq!(None, parse("3年"));
+        assert_eq!(None, parse("13月1日"));
+        assert_eq!(None, parse("2023年2月31日"));
+        assert_eq!(None, parse("2023-02-29"));
+        assert_eq!(None, parse("4月31日"));
+        assert_eq!(Some((10, "2024-02-29".to_string())), parse("2024-02-29"));
+        assert_eq!(Some((5, "--02-29".to_string())), parse("2月29日"));
+        assert_eq!(None, parse("1900年2月29日"));
+        assert_eq!(Some((5, "00:00".to_string())), parse("凌晨12点"));
+        assert_eq!(Some((5, "00:00".to_string())), parse("晚上12点"));
+        assert_eq!(Some((5, "00:00".to_string())), parse("半夜12点"));
+        assert_eq!(Some((4, "02:00".to_string())), parse("半夜2点"));
+        assert_eq!(Some((4, "20:00".to_string())), parse("晚上8点"));
+        assert_eq!(Some((5, "12:00".to_string())), parse("中午12点"));
+        assert_eq!(None, parse("25:00"));
+        assert_eq!(None, parse("24:30"));
+        assert_eq!(Some((5, "24:00".to_string())), parse("24:00"));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::core::lexeme::Lexeme;
+use crate::core::lexeme_path::{LexemePath, PathScore};
+use std::fmt::{Display, Formatter};
+
+/// how a text was segmented, see `IKSegmenter::explain`
+#[derive(Debug, Clone, Default)]
+pub struct Explanation {
+    /// raw lexemes per segmenter, in segmenter order. a lexeme at the same
+    /// position as one of an earlier segmenter is not listed again
+    pub segmenter_lexemes: Vec<(String, Vec<Lexeme>)>,
+    pub cross_paths: Vec<CrossPathExplanation>,
+    /// lexemes of the chosen paths which were dropped as stop words
+    pub stop_words: Vec<Lexeme>,
+    pub tokens: Vec<Lexeme>,
+}
+
+/// one group of crossed lexemes and how the arbitrator resolved it
+#[derive(Debug, Clone, Default)]
+pub struct CrossPathExplanation {
+    pub lexemes: Vec<Lexeme>,
+    /// paths considered by judge, best first. empty if the group was kept
+    /// as it is (INDEX mode or a single lexeme)
+    pub candidates: Vec<CandidatePath>,
+    pub chosen: Vec<Lexeme>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CandidatePath {
+    pub lexemes: Vec<Lexeme>,
+    pub score: PathScore,
+}
+
+impl CandidatePath {
+    pub(crate) fn new(path: &LexemePath, input: &str) -> Self {
+        CandidatePath {
+            lexemes: path_lexemes(path, input),
+            score: path.score(),
+        }
+    }
+}
+
+pub(crate) fn path_lexemes(path: &LexemePath, input: &str) -> Vec<Lexeme> {
+    path.lexeme_list
+        .iter()
+        .map(|lexeme| {
+            let mut lexeme = lexeme.clone();
+            lexeme.parse_lexeme_text(input);
+            lexeme
+        })
+        .collect()
+}
+
+fn lexeme_texts(lexemes: &[Lexeme]) -> String {
+    lexemes
+        .iter()
+        .map(|l| format!("{}[{}..{}]", l.lexeme_text(), l.begin_pos(), l.end_pos()))
+        .collect::<Vec<String>>()
+        .join(" | ")
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (name, lexemes) in self.segmenter_lexemes.iter() {
+            writeln!(f, "{}: {}", name, lexeme_texts(lexemes))?;
+        }
+        for (i, cross_path) in self.cross_paths.iter().enumerate() {
+            writeln!(f, "cross path {}: {}", i, lexeme_texts(&cross_path.lexemes))?;
+            for candidate in cross_path.candidates.iter() {
+                writeln!(
+                    f,
+                    "  candidate: {} (payload_len:{}, x_weight:{}, p_weight:{})",
+                    lexeme_texts(&candidate.lexemes),
+                    candidate.score.payload_len,
+                    candidate.score.x_weight,
+                    candidate.score.p_weight
+                )?;
+            }
+            writeln!(f, "  chosen: {}", lexeme_texts(&cross_path.chosen))?;
+        }
+        writeln!(f, "stop words: {}", lexeme_texts(&self.stop_words))?;
+        write!(f, "tokens: {}", lexeme_texts(&self.tokens))
+    }
+}
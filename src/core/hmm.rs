@@ -0,0 +1,157 @@
+use crate::config::configuration::Configuration;
+use crate::config::default_config::DefaultConfig;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::ops::Range;
+
+// B M E S states, in the order of the model file
+const B: usize = 0;
+const E: usize = 1;
+const M: usize = 2;
+const S: usize = 3;
+const STATES: [usize; 4] = [B, E, M, S];
+const MIN_PROB: f64 = -3.14e100;
+
+/// the model of `hmm_model` in ik.yml, None if not configured
+pub static GLOBAL_HMM: Lazy<Option<HmmModel>> = Lazy::new(|| {
+    let cfg = DefaultConfig::new();
+    cfg.get_hmm_model().map(|model| HmmModel::load(model.as_str()))
+});
+
+/// Hidden markov model tagging chars with Begin/Middle/End/Single of a word
+pub struct HmmModel {
+    start_prob: [f64; 4],
+    trans_prob: [[f64; 4]; 4],
+    emit_prob: [HashMap<char, f64>; 4],
+}
+
+impl HmmModel {
+    pub fn load(file_path: &str) -> Self {
+        let file = File::open(file_path)
+            .unwrap_or_else(|e| panic!("open hmm model:{} error:{}", file_path, e));
+        let mut lines = Vec::new();
+        for line in BufReader::new(file).lines() {
+            match line {
+                Ok(line) => {
+                    let line = line.trim().to_string();
+                    if !line.is_empty() && !line.starts_with('#') {
+                        lines.push(line);
+                    }
+                }
+                Err(e) => {
+                    panic!("read hmm model:{} error:{}", file_path, e);
+                }
+            }
+        }
+        if lines.len() != 9 {
+            panic!(
+                "hmm model:{} expect 9 lines, found {}",
+                file_path,
+                lines.len()
+            );
+        }
+        let mut model = HmmModel {
+            start_prob: [MIN_PROB; 4],
+            trans_prob: [[MIN_PROB; 4]; 4],
+            emit_prob: Default::default(),
+        };
+        model.start_prob = parse_probs(&lines[0]);
+        for state in STATES {
+            model.trans_prob[state] = parse_probs(&lines[1 + state]);
+            for char_prob in lines[5 + state].split(',') {
+                if let Some((c, prob)) = char_prob.split_once(':') {
+                    if let Some(c) = c.chars().next() {
+                        model.emit_prob[state].insert(c, prob.parse().unwrap_or(MIN_PROB));
+                    }
+                }
+            }
+        }
+        log::debug!("after load hmm model:{}", file_path);
+        model
+    }
+
+    fn emit(&self, state: usize, c: char) -> f64 {
+        *self.emit_prob[state].get(&c).unwrap_or(&MIN_PROB)
+    }
+
+    /// viterbi, returns the words of chars as ranges
+    pub fn cut(&self, chars: &[char]) -> Vec<Range<usize>> {
+        if chars.is_empty() {
+            return Vec::new();
+        }
+        // states allowed before a state
+        let prev_states = |state: usize| match state {
+            B => [E, S],
+            M => [M, B],
+            S => [S, E],
+            _ => [B, M],
+        };
+        let mut weights = vec![[MIN_PROB; 4]; chars.len()];
+        let mut paths = vec![[0usize; 4]; chars.len()];
+        for state in STATES {
+            weights[0][state] = self.start_prob[state] + self.emit(state, chars[0]);
+        }
+        for i in 1..chars.len() {
+            for state in STATES {
+                let emit = self.emit(state, chars[i]);
+                let mut best = (f64::MIN, B);
+                for prev in prev_states(state) {
+                    let prob = weights[i - 1][prev] + self.trans_prob[prev][state] + emit;
+                    if prob > best.0 {
+                        best = (prob, prev);
+                    }
+                }
+                weights[i][state] = best.0;
+                paths[i][state] = best.1;
+            }
+        }
+        // a word ends with E or S
+        let last = chars.len() - 1;
+        let mut state = S;
+        if weights[last][E] >= weights[last][S] {
+            state = E;
+        }
+        let mut tags = vec![S; chars.len()];
+        for i in (0..chars.len()).rev() {
+            tags[i] = state;
+            state = paths[i][state];
+        }
+
+        let mut words = Vec::new();
+        let mut begin = 0;
+        for (i, tag) in tags.iter().enumerate() {
+            match *tag {
+                B => begin = i,
+                E => words.push(begin..i + 1),
+                S => words.push(i..i + 1),
+                _ => {}
+            }
+        }
+        words
+    }
+}
+
+fn parse_probs(line: &str) -> [f64; 4] {
+    let mut probs = [MIN_PROB; 4];
+    for (i, prob) in line.split_whitespace().take(4).enumerate() {
+        probs[i] = prob.parse().unwrap_or(MIN_PROB);
+    }
+    probs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hmm_cut() {
+        let chars: Vec<char> = "韩冰是好人".chars().collect();
+        let hmm = GLOBAL_HMM.as_ref().unwrap();
+        let words = hmm.cut(&chars);
+        assert_eq!(0..2, words[0]);
+        let words = hmm.cut(&['好']);
+        assert_eq!(vec![0..1], words);
+    }
+}
@@ -2,67 +2,174 @@ use crate::core::ik_segmenter::TokenMode;
 use crate::core::lexeme::Lexeme;
 use crate::core::lexeme_path::LexemePath;
 use crate::core::ordered_linked_list::{Link, OrderedLinkedList};
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{HashMap, HashSet};
+
+/// judge gives up enumerating the candidate paths of a cross path longer than this
+pub const DEFAULT_MAX_CROSS_LEN: usize = 64;
+/// judge gives up enumerating after this many candidate paths
+pub const DEFAULT_MAX_CANDIDATE_PATHS: usize = 256;
+
+/// a cross path and the candidate paths judged for it, best first, none if
+/// the cross path is kept as it is (INDEX mode or a single lexeme)
+pub struct ArbitratedPath {
+    pub cross_path: LexemePath,
+    pub candidates: Vec<LexemePath>,
+}
+
+impl ArbitratedPath {
+    /// the path taken for the cross path, the best candidate if judged
+    pub fn chosen(&self) -> &LexemePath {
+        self.candidates.first().unwrap_or(&self.cross_path)
+    }
+
+    pub fn into_chosen(self) -> LexemePath {
+        self.candidates
+            .into_iter()
+            .next()
+            .unwrap_or(self.cross_path)
+    }
+}
+
+/// Resolves crossed lexemes into not crossed paths.
+///
+/// Enumerating the candidate paths of a cross path is quadratic in its
+/// lexemes, so it is bounded by `max_cross_len` (chars covered by the cross
+/// path) and `max_candidate_paths`. When either budget is exceeded the cross
+/// path falls back to greedy longest match: from left to right, the longest
+/// lexeme not crossing the ones already taken.
+pub struct IKArbitrator {
+    max_cross_len: usize,
+    max_candidate_paths: usize,
+}
+
+impl Default for IKArbitrator {
+    fn default() -> Self {
+        IKArbitrator::new(DEFAULT_MAX_CROSS_LEN, DEFAULT_MAX_CANDIDATE_PATHS)
+    }
+}
 
-#[derive(Default)]
-pub struct IKArbitrator {}
 impl IKArbitrator {
+    pub fn new(max_cross_len: usize, max_candidate_paths: usize) -> Self {
+        IKArbitrator {
+            max_cross_len,
+            max_candidate_paths: max_candidate_paths.max(1),
+        }
+    }
+
     pub fn process(
         &self,
         orgin_lexemes: &OrderedLinkedList<Lexeme>,
         mode: &TokenMode,
     ) -> HashMap<usize, LexemePath> {
         let mut path_map = HashMap::<usize, LexemePath>::new();
-        let mut cross_path = LexemePath::new();
-        for org_lexeme in orgin_lexemes.iter() {
-            if !cross_path.add_cross_lexeme(org_lexeme) {
-                if self.need_add_path(&cross_path, mode) {
-                    path_map.insert(cross_path.begin() as usize, cross_path);
+        for arbitrated_path in self.arbitrate(orgin_lexemes, mode) {
+            let path = arbitrated_path.into_chosen();
+            path_map.insert(path.begin() as usize, path);
+        }
+        path_map
+    }
+
+    /// every cross path with the candidate paths judged, the paths `process`
+    /// chooses from
+    pub fn arbitrate(
+        &self,
+        orgin_lexemes: &OrderedLinkedList<Lexeme>,
+        mode: &TokenMode,
+    ) -> Vec<ArbitratedPath> {
+        self.cross_paths(orgin_lexemes)
+            .into_iter()
+            .map(|cross_path| {
+                let candidates = if self.need_add_path(&cross_path, mode) {
+                    Vec::new()
                 } else {
-                    let judge_result = self.judge(cross_path.head_node());
-                    if let Some(path) = judge_result {
-                        path_map.insert(path.begin() as usize, path);
+                    self.candidate_paths(&cross_path)
+                };
+                ArbitratedPath {
+                    cross_path,
+                    candidates,
+                }
+            })
+            .collect()
+    }
+
+    /// top-n complete paths over the whole input, each one made of a
+    /// candidate path of every cross path, best first
+    pub fn process_nbest(
+        &self,
+        orgin_lexemes: &OrderedLinkedList<Lexeme>,
+        n: usize,
+    ) -> Vec<LexemePath> {
+        let mut nbest = vec![LexemePath::new()];
+        for cross_path in self.cross_paths(orgin_lexemes) {
+            let candidates = self.candidate_paths(&cross_path);
+            let mut extended = Vec::with_capacity(nbest.len() * candidates.len());
+            for prefix in nbest.iter() {
+                for candidate in candidates.iter() {
+                    let mut path = prefix.clone();
+                    for lexeme in candidate.lexeme_list.iter() {
+                        path.add_not_cross_lexeme(lexeme);
                     }
+                    extended.push(path);
                 }
+            }
+            extended.sort();
+            extended.truncate(n);
+            nbest = extended;
+        }
+        nbest.truncate(n);
+        nbest
+    }
+
+    /// split lexemes into groups of crossed lexemes
+    pub fn cross_paths(&self, orgin_lexemes: &OrderedLinkedList<Lexeme>) -> Vec<LexemePath> {
+        let mut cross_paths = Vec::new();
+        let mut cross_path = LexemePath::new();
+        for org_lexeme in orgin_lexemes.iter() {
+            if !cross_path.add_cross_lexeme(org_lexeme) {
+                cross_paths.push(cross_path);
                 cross_path = LexemePath::new();
                 cross_path.add_cross_lexeme(org_lexeme);
             }
         }
-        if self.need_add_path(&cross_path, mode) {
-            path_map.insert(cross_path.begin() as usize, cross_path);
-        } else {
-            let judge_result = self.judge(cross_path.head_node());
-            if let Some(path) = judge_result {
-                path_map.insert(path.begin() as usize, path);
-            }
+        if cross_path.len() > 0 {
+            cross_paths.push(cross_path);
         }
-        path_map
+        cross_paths
     }
 
-    fn judge(&self, cur_node: Option<&Link<Lexeme>>) -> Option<LexemePath> {
-        let mut path_options = BTreeSet::new();
+    /// all not crossed paths of a cross path, best first. only the greedy
+    /// longest match path if the budget is exceeded
+    pub fn candidate_paths(&self, cross_path: &LexemePath) -> Vec<LexemePath> {
         let mut option_path = LexemePath::new();
-        let mut lexeme_stack = self.forward_path(cur_node, &mut option_path);
-        path_options.insert(option_path.clone());
+        let mut lexeme_stack = self.forward_path(cross_path.head_node(), &mut option_path);
+        if cross_path.path_len() > self.max_cross_len {
+            log::debug!("cross path too long, fallback to greedy: {}", option_path);
+            return vec![option_path];
+        }
+        let greedy_path = option_path.clone();
+        let mut path_options = vec![option_path.clone()];
+        let mut found_paths = HashSet::new();
+        found_paths.insert(option_path.clone());
         while let Some(node) = lexeme_stack.pop() {
             self.back_path(node, &mut option_path);
             self.forward_path(node, &mut option_path);
-            path_options.insert(option_path.clone());
-        }
-        // pick first one as best
-        let mut best_path = None;
-        if !path_options.is_empty() {
-            for o in path_options.into_iter() {
-                best_path = Some(o);
-                break;
+            if found_paths.insert(option_path.clone()) {
+                if path_options.len() >= self.max_candidate_paths {
+                    log::debug!(
+                        "too many candidate paths, fallback to greedy: {}",
+                        greedy_path
+                    );
+                    return vec![greedy_path];
+                }
+                path_options.push(option_path.clone());
             }
         }
-        best_path
-        // after rust 1.66.0
-        // path_options.pop_first()
+        // stable sort keeps the first found one among equal weighted paths
+        path_options.sort();
+        path_options
     }
 
-    fn need_add_path(&self, cross_path: &LexemePath, mode: &TokenMode) -> bool {
+    pub fn need_add_path(&self, cross_path: &LexemePath, mode: &TokenMode) -> bool {
         match mode {
             TokenMode::INDEX => return true,
             _ => {}
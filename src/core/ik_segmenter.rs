@@ -1,23 +1,225 @@
-use crate::core::char_util::{regularize_str, utf8_len, CharType};
+use crate::core::char_util::{is_emoji, is_punctuation, is_symbol, utf8_len, utf8_slice, CharType};
 use crate::core::cjk_segmenter::CJKSegmenter;
 use crate::core::cn_quantifier_segmenter::CnQuantifierSegmenter;
-use crate::core::ik_arbitrator::IKArbitrator;
+use crate::core::contact_segmenter::{sub_token_ranges, ContactSegmenter};
+use crate::core::date_segmenter::DateSegmenter;
+use crate::core::explanation::{path_lexemes, CandidatePath, CrossPathExplanation, Explanation};
+use crate::core::hmm::GLOBAL_HMM;
+use crate::core::ik_arbitrator::{
+    IKArbitrator, DEFAULT_MAX_CANDIDATE_PATHS, DEFAULT_MAX_CROSS_LEN,
+};
 use crate::core::letter_segmentor::LetterSegmenter;
 use crate::core::lexeme::{Lexeme, LexemeType};
-use crate::core::lexeme_path::LexemePath;
+use crate::core::lexeme_path::{LexemePath, PathScore};
+use crate::core::normalizer::{
+    IncompatibleNormalizationError, Normalization, NormalizedText, GLOBAL_NORMALIZATION,
+};
+use crate::core::number_util::parse_decimal;
 use crate::core::ordered_linked_list::OrderedLinkedList;
+use crate::core::other_cjk_segmenter::OtherCjkSegmenter;
 use crate::core::segmentor::Segmenter;
+use crate::core::word_delimiter::WordDelimiter;
 use crate::dict::dictionary::GLOBAL_DICT;
-use std::collections::{HashMap, LinkedList};
+use std::collections::{HashMap, HashSet, LinkedList};
+use rust_stemmers::{Algorithm, Stemmer};
+use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Debug, Clone)]
 pub enum TokenMode {
     INDEX,
     SEARCH,
 }
+#[derive(Debug, Clone)]
+pub struct IKSegmenterBuilder {
+    max_cross_len: usize,
+    max_candidate_paths: usize,
+    recognize_oov: bool,
+    cjk_bigram: bool,
+    emit_arabic_number: bool,
+    contact_sub_tokens: bool,
+    emit_symbols: bool,
+    emit_original_script: bool,
+    stem_english: bool,
+    remove_english_stop_words: bool,
+    word_delimiter: Option<WordDelimiter>,
+    normalization: Normalization,
+}
+
+impl Default for IKSegmenterBuilder {
+    fn default() -> Self {
+        IKSegmenterBuilder {
+            max_cross_len: DEFAULT_MAX_CROSS_LEN,
+            max_candidate_paths: DEFAULT_MAX_CANDIDATE_PATHS,
+            recognize_oov: false,
+            cjk_bigram: false,
+            emit_arabic_number: false,
+            contact_sub_tokens: false,
+            emit_symbols: false,
+            emit_original_script: false,
+            stem_english: false,
+            remove_english_stop_words: false,
+            word_delimiter: None,
+            normalization: GLOBAL_NORMALIZATION.clone(),
+        }
+    }
+}
+
+impl IKSegmenterBuilder {
+    /// cross paths covering more chars than this are not judged, but
+    /// segmented by greedy longest match
+    pub fn max_cross_len(mut self, max_cross_len: usize) -> Self {
+        self.max_cross_len = max_cross_len;
+        self
+    }
+
+    /// a cross path with more candidate paths than this is segmented by
+    /// greedy longest match
+    pub fn max_candidate_paths(mut self, max_candidate_paths: usize) -> Self {
+        self.max_candidate_paths = max_candidate_paths;
+        self
+    }
+
+    /// merge runs of single chars not covered by the dictionary into
+    /// CNOOV words by the hidden markov model of `hmm_model` in ik.yml, if
+    /// one is configured
+    pub fn recognize_oov(mut self, recognize_oov: bool) -> Self {
+        self.recognize_oov = recognize_oov;
+        self
+    }
+
+    /// emit overlapping bigrams instead of single chars for runs of
+    /// CNCHAR/OtherCJK chars not covered by the dictionary
+    pub fn cjk_bigram(mut self, cjk_bigram: bool) -> Self {
+        self.cjk_bigram = cjk_bigram;
+        self
+    }
+
+    /// also emit the arabic form of CNUM/CQUAN lexemes, like "960" for
+    /// "九百六十", as an ARABIC lexeme at the same position
+    pub fn emit_arabic_number(mut self, emit_arabic_number: bool) -> Self {
+        self.emit_arabic_number = emit_arabic_number;
+        self
+    }
+
+    /// also emit the user and domain of EMAIL lexemes and the host of URL
+    /// lexemes as LETTER lexemes
+    pub fn contact_sub_tokens(mut self, contact_sub_tokens: bool) -> Self {
+        self.contact_sub_tokens = contact_sub_tokens;
+        self
+    }
+
+    /// emit punctuation, symbols and emoji not covered by other lexemes as
+    /// PUNCTUATION, SYMBOL and EMOJI lexemes, one per grapheme cluster
+    pub fn emit_symbols(mut self, emit_symbols: bool) -> Self {
+        self.emit_symbols = emit_symbols;
+        self
+    }
+
+    /// also emit lexemes converted from traditional chinese in their
+    /// original script, like "資訊" for "资讯", at the same position
+    pub fn emit_original_script(mut self, emit_original_script: bool) -> Self {
+        self.emit_original_script = emit_original_script;
+        self
+    }
+
+    /// stem ENGLISH lexemes by the snowball english stemmer, "running" ->
+    /// "run"
+    pub fn stem_english(mut self, stem_english: bool) -> Self {
+        self.stem_english = stem_english;
+        self
+    }
+
+    /// remove ENGLISH lexemes found in the english stop word dict of ik.yml,
+    /// like "the" and "of"
+    pub fn remove_english_stop_words(mut self, remove_english_stop_words: bool) -> Self {
+        self.remove_english_stop_words = remove_english_stop_words;
+        self
+    }
+
+    /// split LETTER and ENGLISH lexemes into their parts by the word
+    /// delimiter, like "galaxy" "s" "24" for "GalaxyS24"
+    pub fn word_delimiter(mut self, word_delimiter: WordDelimiter) -> Self {
+        self.word_delimiter = Some(word_delimiter);
+        self
+    }
+
+    /// how the text is normalized before segmentation, defaults to the
+    /// `normalization` section of ik.yml, token positions always refer to
+    /// the original text, an error if the words of the dictionary, normalized
+    /// by ik.yml, would not match the text normalized by it
+    pub fn normalization(
+        mut self,
+        normalization: Normalization,
+    ) -> Result<Self, IncompatibleNormalizationError> {
+        let compatible = {cfg_if::cfg_if! {
+            if #[cfg(feature="use-parking-lot")] {GLOBAL_DICT.read().is_normalized_by(&normalization)}
+            else /*if #[cfg(feature="use-std-sync")]*/ {
+                GLOBAL_DICT
+                    .read()
+                    .map_or(false, |dict| dict.is_normalized_by(&normalization))
+            }
+        }};
+        if !compatible {
+            return Err(IncompatibleNormalizationError);
+        }
+        self.normalization = normalization;
+        Ok(self)
+    }
+
+    pub fn build(self) -> IKSegmenter {
+        IKSegmenter {
+            arbitrator: IKArbitrator::new(self.max_cross_len, self.max_candidate_paths),
+            recognize_oov: self.recognize_oov,
+            cjk_bigram: self.cjk_bigram,
+            emit_arabic_number: self.emit_arabic_number,
+            contact_sub_tokens: self.contact_sub_tokens,
+            emit_symbols: self.emit_symbols,
+            emit_original_script: self.emit_original_script,
+            english_stemmer: if self.stem_english {
+                Some(Stemmer::create(Algorithm::English))
+            } else {
+                None
+            },
+            remove_english_stop_words: self.remove_english_stop_words,
+            word_delimiter: self.word_delimiter,
+            normalization: self.normalization,
+        }
+    }
+}
+
+/// one complete segmentation of a text and the weights it was ranked by
+#[derive(Debug, Clone)]
+pub struct Segmentation {
+    lexemes: Vec<Lexeme>,
+    score: PathScore,
+}
+
+impl Segmentation {
+    pub fn lexemes(&self) -> &[Lexeme] {
+        &self.lexemes
+    }
+
+    /// the weights of the dictionary lexemes the segmentation was ranked by,
+    /// the single chars filling the gaps between them are not counted, so
+    /// `lexeme_count` may be less than the number of `lexemes`
+    pub fn score(&self) -> &PathScore {
+        &self.score
+    }
+}
+
 // ik main class
 pub struct IKSegmenter {
     arbitrator: IKArbitrator,
+    recognize_oov: bool,
+    cjk_bigram: bool,
+    emit_arabic_number: bool,
+    contact_sub_tokens: bool,
+    emit_symbols: bool,
+    emit_original_script: bool,
+    english_stemmer: Option<Stemmer>,
+    remove_english_stop_words: bool,
+    word_delimiter: Option<WordDelimiter>,
+    normalization: Normalization,
 }
 
 unsafe impl Send for IKSegmenter {}
@@ -25,60 +227,206 @@ unsafe impl Sync for IKSegmenter {}
 
 impl IKSegmenter {
     pub fn new() -> Self {
-        let ik = IKSegmenter {
-            arbitrator: IKArbitrator::default(),
-        };
-        ik
+        IKSegmenter::builder().build()
+    }
+
+    pub fn builder() -> IKSegmenterBuilder {
+        IKSegmenterBuilder::default()
     }
 
     fn new_segmenters() -> Vec<Box<dyn Segmenter>> {
         vec![
+            Box::new(DateSegmenter::default()),
+            Box::new(ContactSegmenter::default()),
             Box::new(LetterSegmenter::default()),
             Box::new(CnQuantifierSegmenter::default()),
+            Box::new(OtherCjkSegmenter::default()),
             Box::new(CJKSegmenter::default()),
         ]
     }
 
     pub fn tokenize(&/*mut*/ self, text: &str, mode: TokenMode) -> Vec<Lexeme> {
-        let regular_str = regularize_str(text);
-        let input = regular_str.as_str();
+        let normalized = self.normalization.normalize(text);
+        let input = normalized.text.as_str();
+        let origin_lexemes = self.segment(input, None);
+        let mut path_map = self.arbitrator.process(&origin_lexemes, &mode);
+        let results = self.output_to_result(&mut path_map, input);
+        let mut tokens = self.finalize(results, &normalized, &mode, None);
+        normalized.restore_positions(&mut tokens);
+        tokens
+    }
+
+    /// top-n segmentations of the text, best first, ranked the same way
+    /// the arbitrator ranks the candidate paths of a cross path
+    pub fn tokenize_nbest(&self, text: &str, n: usize) -> Vec<Segmentation> {
+        let normalized = self.normalization.normalize(text);
+        let input = normalized.text.as_str();
+        let origin_lexemes = self.segment(input, None);
+        let nbest_paths = self.arbitrator.process_nbest(&origin_lexemes, n);
+        let mut segmentations = Vec::with_capacity(nbest_paths.len());
+        for path in nbest_paths.into_iter() {
+            let score = path.score();
+            let mut path_map = HashMap::new();
+            path_map.insert(path.begin() as usize, path);
+            let results = self.output_to_result(&mut path_map, input);
+            let mut lexemes = self.finalize(results, &normalized, &TokenMode::SEARCH, None);
+            normalized.restore_positions(&mut lexemes);
+            segmentations.push(Segmentation { lexemes, score });
+        }
+        segmentations
+    }
+
+    /// candidates, cross paths and the chosen paths of the segmentation,
+    /// for finding out why a text is segmented the way it is
+    pub fn explain(&self, text: &str, mode: TokenMode) -> Explanation {
+        let normalized = self.normalization.normalize(text);
+        let input = normalized.text.as_str();
+        let mut explanation = Explanation::default();
+        let origin_lexemes = self.segment(input, Some(&mut explanation.segmenter_lexemes));
+        for (_, lexemes) in explanation.segmenter_lexemes.iter_mut() {
+            for lexeme in lexemes.iter_mut() {
+                lexeme.parse_lexeme_text(input);
+            }
+        }
+
+        let mut path_map = HashMap::new();
+        for arbitrated_path in self.arbitrator.arbitrate(&origin_lexemes, &mode) {
+            let candidates = arbitrated_path
+                .candidates
+                .iter()
+                .map(|candidate| CandidatePath::new(candidate, input))
+                .collect();
+            explanation.cross_paths.push(CrossPathExplanation {
+                lexemes: path_lexemes(&arbitrated_path.cross_path, input),
+                candidates,
+                chosen: path_lexemes(arbitrated_path.chosen(), input),
+            });
+            let path = arbitrated_path.into_chosen();
+            path_map.insert(path.begin() as usize, path);
+        }
+
+        let results = self.output_to_result(&mut path_map, input);
+        explanation.tokens = self.finalize(
+            results,
+            &normalized,
+            &mode,
+            Some(&mut explanation.stop_words),
+        );
+        for (_, lexemes) in explanation.segmenter_lexemes.iter_mut() {
+            normalized.restore_positions(lexemes);
+        }
+        for cross_path in explanation.cross_paths.iter_mut() {
+            normalized.restore_positions(&mut cross_path.lexemes);
+            normalized.restore_positions(&mut cross_path.chosen);
+            for candidate in cross_path.candidates.iter_mut() {
+                normalized.restore_positions(&mut candidate.lexemes);
+            }
+        }
+        normalized.restore_positions(&mut explanation.stop_words);
+        normalized.restore_positions(&mut explanation.tokens);
+        explanation
+    }
+
+    fn segment(
+        &self,
+        input: &str,
+        mut trace: Option<&mut Vec<(String, Vec<Lexeme>)>>,
+    ) -> OrderedLinkedList<Lexeme> {
         let mut origin_lexemes = OrderedLinkedList::<Lexeme>::new();
         let mut segmenters = IKSegmenter::new_segmenters();
+        if let Some(trace) = trace.as_mut() {
+            for segmenter in segmenters.iter() {
+                trace.push((segmenter.name().to_string(), Vec::new()));
+            }
+        }
+        let mut traced = HashSet::new();
         for (cursor, curr_char) in input.chars().enumerate() {
             let curr_char_type = CharType::from(curr_char);
-            for segmenter in segmenters.iter_mut() {
+            for (i, segmenter) in segmenters.iter_mut().enumerate() {
+                let size = origin_lexemes.len();
                 segmenter.analyze(input, cursor, &curr_char_type, &mut origin_lexemes);
+                match trace.as_mut() {
+                    Some(trace) if origin_lexemes.len() > size => {
+                        for lexeme in origin_lexemes.iter() {
+                            if traced.insert((lexeme.begin_pos(), lexeme.end_pos())) {
+                                trace[i].1.push(lexeme.clone());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
             }
         }
+        origin_lexemes
+    }
 
-        let mut path_map = self.arbitrator.process(&origin_lexemes, &mode);
-        let mut results = self.output_to_result(&mut path_map, input);
+    fn finalize(
+        &self,
+        mut results: LinkedList<Lexeme>,
+        normalized: &NormalizedText,
+        mode: &TokenMode,
+        mut stop_words: Option<&mut Vec<Lexeme>>,
+    ) -> Vec<Lexeme> {
+        let input = normalized.text.as_str();
         let mut final_results = Vec::with_capacity(results.len());
         // remove stop word
         let mut result = results.pop_front();
         while let Some(ref mut result_value) = result {
-            match &mode {
+            match mode {
                 TokenMode::SEARCH => {
-                    self.compound(&mut results, result_value);
+                    self.compound(&mut results, result_value, input);
                 }
                 _ => {}
             }
+            if let LexemeType::CNUM | LexemeType::DECIMAL = result_value.lexeme_type() {
+                result_value.set_value(self.number_value(result_value, input));
+            }
 
             let lock_guard = {cfg_if::cfg_if! {
                 if #[cfg(feature="use-parking-lot")] {Some(GLOBAL_DICT.read())}
                 else /*if #[cfg(feature="use-std-sync")]*/ {GLOBAL_DICT.read().map_or(None,|x|Some(x))}
             }};
-            if lock_guard.is_none() || !lock_guard.is_some_and(|x|x.is_stop_word(
-                    input,
-                    result_value.begin_pos(),
-                    result_value.len()))
+            let english = matches!(result_value.lexeme_type(), LexemeType::ENGLISH);
+            let english_stop_word = english
+                && self.remove_english_stop_words
+                && lock_guard.as_ref().is_some_and(|x| {
+                    x.is_english_stop_word(utf8_slice(
+                        input,
+                        result_value.begin_pos(),
+                        result_value.end_pos(),
+                    ))
+                });
+            if !english_stop_word
+                && (lock_guard.is_none()
+                    || !lock_guard.is_some_and(|x| {
+                        x.is_stop_word(input, result_value.begin_pos(), result_value.len())
+                    }))
             {
                 result_value.parse_lexeme_text(input);
-                final_results.push(result_value.clone())
+                if let Some(stemmer) = self.english_stemmer.as_ref().filter(|_| english) {
+                    let stemmed = stemmer.stem(result_value.lexeme_text()).into_owned();
+                    result_value.set_lexeme_text(&stemmed);
+                }
+                final_results.push(result_value.clone());
+                if self.emit_arabic_number {
+                    self.add_arabic_lexeme(&mut final_results, result_value);
+                }
+                if self.contact_sub_tokens {
+                    self.add_sub_lexemes(&mut final_results, result_value, input);
+                }
+                if self.emit_original_script {
+                    self.add_original_script_lexeme(&mut final_results, result_value, normalized);
+                }
+            } else if let Some(stop_words) = stop_words.as_mut() {
+                result_value.parse_lexeme_text(input);
+                stop_words.push(result_value.clone());
             }
             result = results.pop_front();
         }
-        final_results
+        match self.word_delimiter.as_ref() {
+            Some(word_delimiter) => word_delimiter.process(final_results, normalized),
+            None => final_results,
+        }
     }
 
     fn output_to_result(
@@ -92,13 +440,7 @@ impl IKSegmenter {
         while index < char_count {
             let curr_char = input.chars().nth(index).unwrap();
             let cur_char_type = CharType::from(curr_char);
-            match cur_char_type {
-                CharType::USELESS => {
-                    index += 1;
-                    continue;
-                }
-                _ => {}
-            }
+            // a path may start at a useless char, like the "$" of "$12"
             let path = path_map.get_mut(&index);
             if let Some(p) = path {
                 let mut cur_lexeme = p.poll_first();
@@ -120,9 +462,154 @@ impl IKSegmenter {
                 index += 1;
             }
         }
+        if self.recognize_oov {
+            results = self.merge_oov(results, input);
+        }
+        if self.cjk_bigram {
+            results = self.bigram(results);
+        }
+        if self.emit_symbols {
+            results = self.add_symbols(results, input);
+        }
         results
     }
 
+    /// add a lexeme for every grapheme cluster of punctuation, symbol or
+    /// emoji chars not covered by the results
+    fn add_symbols(&self, results: LinkedList<Lexeme>, input: &str) -> LinkedList<Lexeme> {
+        // the digit of a keycap emoji like "1️⃣" is part of the emoji
+        let mut keycaps = Vec::new();
+        let mut begin = 0;
+        for grapheme in input.graphemes(true) {
+            let end = begin + utf8_len(grapheme);
+            if grapheme.ends_with('\u{20E3}') {
+                keycaps.push(begin..end);
+            }
+            begin = end;
+        }
+        let results: LinkedList<Lexeme> = results
+            .into_iter()
+            .filter(|lexeme| {
+                !keycaps.iter().any(|keycap| {
+                    keycap.start <= lexeme.begin_pos() && lexeme.end_pos() <= keycap.end
+                })
+            })
+            .collect();
+        let mut covered = vec![false; utf8_len(input)];
+        for lexeme in results.iter() {
+            covered[lexeme.begin_pos()..lexeme.end_pos()].fill(true);
+        }
+        let mut symbols = LinkedList::new();
+        let mut begin = 0;
+        for grapheme in input.graphemes(true) {
+            let end = begin + utf8_len(grapheme);
+            let first = grapheme.chars().next().unwrap_or(' ');
+            let lexeme_type = if covered[begin..end].iter().any(|c| *c) {
+                None
+            } else if is_emoji(grapheme) || grapheme.ends_with('\u{20E3}') {
+                Some(LexemeType::EMOJI)
+            } else if is_punctuation(first) {
+                Some(LexemeType::PUNCTUATION)
+            } else if is_symbol(first) {
+                Some(LexemeType::SYMBOL)
+            } else {
+                None
+            };
+            if let Some(lexeme_type) = lexeme_type {
+                symbols.push_back(Lexeme::new(begin..end, lexeme_type));
+            }
+            begin = end;
+        }
+
+        // merge by begin position
+        let mut merged = LinkedList::new();
+        let mut results = results;
+        while let Some(symbol) = symbols.pop_front() {
+            while results
+                .front()
+                .is_some_and(|lexeme| lexeme.begin_pos() < symbol.begin_pos())
+            {
+                merged.push_back(results.pop_front().unwrap());
+            }
+            merged.push_back(symbol);
+        }
+        merged.append(&mut results);
+        merged
+    }
+
+    /// run the hmm over consecutive CNCHAR lexemes
+    fn merge_oov(&self, results: LinkedList<Lexeme>, input: &str) -> LinkedList<Lexeme> {
+        let Some(hmm) = GLOBAL_HMM.as_ref() else {
+            return results;
+        };
+        let chars: Vec<char> = input.chars().collect();
+        let is_cn_char = |t: &LexemeType| matches!(t, LexemeType::CNCHAR);
+        self.rewrite_single_runs(results, is_cn_char, |run| {
+            let begin = run[0].begin_pos();
+            let end = run[run.len() - 1].end_pos();
+            let mut words = Vec::new();
+            for word in hmm.cut(&chars[begin..end]) {
+                let lexeme_type = if word.len() > 1 {
+                    LexemeType::CNOOV
+                } else {
+                    LexemeType::CNCHAR
+                };
+                words.push(Lexeme::new(
+                    begin + word.start..begin + word.end,
+                    lexeme_type,
+                ));
+            }
+            words
+        })
+    }
+
+    /// overlapping bigrams of consecutive CNCHAR/OtherCJK lexemes
+    fn bigram(&self, results: LinkedList<Lexeme>) -> LinkedList<Lexeme> {
+        let is_single_char =
+            |t: &LexemeType| matches!(t, LexemeType::CNCHAR | LexemeType::OtherCJK);
+        self.rewrite_single_runs(results, is_single_char, |run| {
+            run.windows(2)
+                .map(|pair| Lexeme::new(pair[0].begin_pos()..pair[1].end_pos(), LexemeType::BIGRAM))
+                .collect()
+        })
+    }
+
+    /// replace every run of at least two adjacent single char lexemes of
+    /// the given types
+    fn rewrite_single_runs<F>(
+        &self,
+        results: LinkedList<Lexeme>,
+        in_run: fn(&LexemeType) -> bool,
+        mut rewrite: F,
+    ) -> LinkedList<Lexeme>
+    where
+        F: FnMut(&[Lexeme]) -> Vec<Lexeme>,
+    {
+        let mut rewritten = LinkedList::new();
+        let mut run: Vec<Lexeme> = Vec::new();
+        let mut flush = |run: &mut Vec<Lexeme>, rewritten: &mut LinkedList<Lexeme>| {
+            if run.len() < 2 {
+                rewritten.extend(run.drain(..));
+            } else {
+                rewritten.extend(rewrite(run));
+                run.clear();
+            }
+        };
+        for lexeme in results.into_iter() {
+            if !in_run(lexeme.lexeme_type()) || lexeme.len() != 1 {
+                flush(&mut run, &mut rewritten);
+                rewritten.push_back(lexeme);
+                continue;
+            }
+            if !run.is_empty() && run[run.len() - 1].end_pos() != lexeme.begin_pos() {
+                flush(&mut run, &mut rewritten);
+            }
+            run.push(lexeme);
+        }
+        flush(&mut run, &mut rewritten);
+        rewritten
+    }
+
     fn add_single_lexeme(
         &self,
         results: &mut LinkedList<Lexeme>,
@@ -145,17 +632,68 @@ impl IKSegmenter {
         });
     }
 
-    fn compound(&self, results: &mut LinkedList<Lexeme>, result: &mut Lexeme) {
+    fn number_value(&self, lexeme: &Lexeme, input: &str) -> Option<String> {
+        let text = utf8_slice(input, lexeme.begin_pos(), lexeme.end_pos());
+        parse_decimal(text)
+    }
+
+    fn add_arabic_lexeme(&self, final_results: &mut Vec<Lexeme>, lexeme: &Lexeme) {
+        match (lexeme.lexeme_type(), lexeme.value()) {
+            (LexemeType::CNUM | LexemeType::CQUAN, Some(value))
+                if value != lexeme.lexeme_text() =>
+            {
+                let mut arabic_lexeme =
+                    Lexeme::new(lexeme.begin_pos()..lexeme.end_pos(), LexemeType::ARABIC);
+                arabic_lexeme.set_lexeme_text(value);
+                final_results.push(arabic_lexeme);
+            }
+            _ => {}
+        }
+    }
+
+    fn add_sub_lexemes(&self, final_results: &mut Vec<Lexeme>, lexeme: &Lexeme, input: &str) {
+        let ranges = sub_token_ranges(lexeme.lexeme_type(), lexeme.lexeme_text());
+        for (begin, end) in ranges {
+            if begin < end {
+                let begin = lexeme.begin_pos() + begin;
+                let end = lexeme.begin_pos() + end;
+                let mut sub_lexeme = Lexeme::new(begin..end, LexemeType::LETTER);
+                sub_lexeme.parse_lexeme_text(input);
+                final_results.push(sub_lexeme);
+            }
+        }
+    }
+
+    fn add_original_script_lexeme(
+        &self,
+        final_results: &mut Vec<Lexeme>,
+        lexeme: &Lexeme,
+        normalized: &NormalizedText,
+    ) {
+        if let Some(text) = normalized.unconverted_text(lexeme.begin_pos(), lexeme.end_pos()) {
+            let mut original_lexeme = lexeme.clone();
+            original_lexeme.set_lexeme_text(text);
+            final_results.push(original_lexeme);
+        }
+    }
+
+    fn compound(&self, results: &mut LinkedList<Lexeme>, result: &mut Lexeme, input: &str) {
         if !results.is_empty() {
             match result.lexeme_type() {
-                LexemeType::ARABIC => {
+                LexemeType::ARABIC | LexemeType::DECIMAL => {
                     let mut append_ok = false;
+                    // "3.5万" stays a DECIMAL
+                    let number_type = match result.lexeme_type() {
+                        LexemeType::DECIMAL => LexemeType::DECIMAL,
+                        _ => LexemeType::CNUM,
+                    };
                     let next_lexeme = results.front();
                     next_lexeme.map(|next| match next.lexeme_type() {
                         LexemeType::CNUM => {
-                            append_ok = result.append(next, LexemeType::CNUM);
+                            append_ok = result.append(next, number_type);
                         }
                         LexemeType::COUNT => {
+                            result.set_value(self.number_value(result, input));
                             append_ok = result.append(next, LexemeType::CQUAN);
                         }
                         _ => {}
@@ -168,11 +706,12 @@ impl IKSegmenter {
             }
 
             match result.lexeme_type() {
-                LexemeType::CNUM if !results.is_empty() => {
+                LexemeType::CNUM | LexemeType::DECIMAL if !results.is_empty() => {
                     let mut append_ok = false;
                     let next_lexeme = results.front();
                     next_lexeme.map(|next| match next.lexeme_type() {
                         LexemeType::COUNT => {
+                            result.set_value(self.number_value(result, input));
                             append_ok = result.append(next, LexemeType::CQUAN);
                         }
                         _ => {}
@@ -190,6 +729,7 @@ impl IKSegmenter {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::core::normalizer::NormalizationForm;
     use log;
     use std::thread;
 
@@ -232,6 +772,519 @@ mod test {
         texts
     }
 
+    #[test]
+    fn test_nbest_segment() {
+        let ik = IKSegmenter::new();
+        for text in _get_input_texts() {
+            let nbest = ik.tokenize_nbest(text, 3);
+            assert!(!nbest.is_empty() && nbest.len() <= 3);
+            let best_texts: Vec<&str> =
+                nbest[0].lexemes().iter().map(|l| l.lexeme_text()).collect();
+            let search_tokens = ik.tokenize(text, TokenMode::SEARCH);
+            let search_texts: Vec<&str> = search_tokens.iter().map(|l| l.lexeme_text()).collect();
+            assert_eq!(search_texts, best_texts);
+            for (i, segmentation) in nbest.iter().enumerate() {
+                log::info!("{} {:?}", i, segmentation.score());
+            }
+        }
+        let nbest = ik.tokenize_nbest("结婚的和尚未结婚的", 5);
+        assert!(nbest.len() > 1);
+        for pair in nbest.windows(2) {
+            assert!(pair[0].score().payload_len >= pair[1].score().payload_len);
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let ik = IKSegmenter::new();
+        let text = "结婚的和尚未结婚的";
+        let explanation = ik.explain(text, TokenMode::SEARCH);
+        log::info!("{}", explanation);
+        let names: Vec<&str> = explanation
+            .segmenter_lexemes
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(
+            vec![
+                "DATE_SEGMENTER",
+                "CONTACT_SEGMENTER",
+                "LETTER_SEGMENTER",
+                "QUAN_SEGMENTER",
+                "OTHER_CJK_SEGMENTER",
+                "CJK_SEGMENTER"
+            ],
+            names
+        );
+        let tokens = ik.tokenize(text, TokenMode::SEARCH);
+        assert_eq!(tokens, explanation.tokens);
+        let judged = explanation
+            .cross_paths
+            .iter()
+            .find(|c| c.chosen.iter().any(|l| l.lexeme_text() == "和尚"))
+            .unwrap();
+        assert!(judged.candidates.len() > 1);
+        assert_eq!(judged.chosen, judged.candidates[0].lexemes);
+
+        let explanation = ik.explain(text, TokenMode::INDEX);
+        assert!(explanation
+            .cross_paths
+            .iter()
+            .all(|c| c.candidates.is_empty()));
+        assert_eq!(ik.tokenize(text, TokenMode::INDEX), explanation.tokens);
+    }
+
+    #[test]
+    fn test_judge_budget() {
+        let text = "结婚的和尚未结婚的";
+        let texts = |ik: &IKSegmenter| {
+            ik.tokenize(text, TokenMode::SEARCH)
+                .iter()
+                .map(|l| l.lexeme_text().to_string())
+                .collect::<Vec<String>>()
+        };
+        let greedy = vec!["结婚", "的", "和尚", "未结", "婚", "的"];
+        assert_eq!(
+            greedy,
+            texts(&IKSegmenter::builder().max_candidate_paths(1).build())
+        );
+        assert_eq!(
+            greedy,
+            texts(&IKSegmenter::builder().max_cross_len(2).build())
+        );
+        assert_eq!(
+            vec!["结婚", "的", "和尚", "未", "结婚", "的"],
+            texts(&IKSegmenter::new())
+        );
+    }
+
+    // "text/TYPE=value@begin..end" of each token, "=value" only if it has one
+    fn describe(tokens: &[Lexeme]) -> Vec<String> {
+        tokens
+            .iter()
+            .map(|t| {
+                let value = t.value().map(|v| format!("={}", v)).unwrap_or_default();
+                format!(
+                    "{}/{}{}@{}..{}",
+                    t.lexeme_text(),
+                    t.lexeme_type().as_str(),
+                    value,
+                    t.begin_pos(),
+                    t.end_pos()
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_typed_segment() {
+        let cases = vec![
+            (
+                "九百六十平方公里",
+                vec!["九百六十/TYPE_CNUM=960@0..4", "平方公里/CN_WORD@4..8"],
+            ),
+            ("一百零五个", vec!["一百零五个/TYPE_CQUAN=105@0..5"]),
+            ("3万", vec!["3万/TYPE_CNUM=30000@0..2"]),
+            (
+                "国庆节是2023年10月1日下午3点半开始",
+                vec![
+                    "国庆节/CN_WORD@0..3",
+                    "是/CN_CHAR@3..4",
+                    "2023年10月1日/DATE=2023-10-01@4..14",
+                    "下午3点半/TIME=15:30@14..19",
+                    "开始/CN_WORD@19..21",
+                ],
+            ),
+            (
+                "会议在2023-10-01 15:30举行",
+                vec![
+                    "会议/CN_WORD@0..2",
+                    "在/CN_CHAR@2..3",
+                    "2023-10-01/DATE=2023-10-01@3..13",
+                    "15:30/TIME=15:30@14..19",
+                    "举行/CN_WORD@19..21",
+                ],
+            ),
+            (
+                "联系zhiyi.shen@gmail.com或访问https://github.com/jobdeng",
+                vec![
+                    "联系/CN_WORD@0..2",
+                    "zhiyi.shen@gmail.com/EMAIL@2..22",
+                    "或/CN_CHAR@22..23",
+                    "访问/CN_WORD@23..25",
+                    "https://github.com/jobdeng/URL@25..51",
+                ],
+            ),
+            (
+                "服务器192.168.1.1，电话13812345678或010-12345678。",
+                vec![
+                    "服务器/CN_WORD@0..3",
+                    "192.168.1.1/IP@3..14",
+                    "电话/CN_WORD@15..17",
+                    "13812345678/PHONE@17..28",
+                    "或/CN_CHAR@28..29",
+                    "010-12345678/PHONE@29..41",
+                ],
+            ),
+            (
+                "增长50%，即百分之五十",
+                vec![
+                    "增长/CN_WORD@0..2",
+                    "50%/PERCENT=50%@2..5",
+                    "即/CN_CHAR@6..7",
+                    "百分之五十/PERCENT=50%@7..12",
+                ],
+            ),
+            (
+                "售价￥3.5，约$12，投资3.5亿元，体重3.5公斤",
+                vec![
+                    "售价/CN_WORD@0..2",
+                    "￥3.5/MONEY=CNY 3.5@2..6",
+                    "约/CN_CHAR@7..8",
+                    "$12/MONEY=USD 12@8..11",
+                    "投资/CN_WORD@12..14",
+                    "3.5亿元/MONEY=CNY 350000000@14..19",
+                    "体重/CN_WORD@20..22",
+                    "3.5公斤/TYPE_CQUAN=3.5@22..27",
+                ],
+            ),
+            (
+                "一元二次方程",
+                vec!["一元/CN_WORD@0..2", "二次方程/CN_WORD@2..6"],
+            ),
+            (
+                "三块钱的东西",
+                vec![
+                    "三块钱/MONEY=CNY 3@0..3",
+                    "的/CN_WORD@3..4",
+                    "东西/CN_WORD@4..6",
+                ],
+            ),
+            (
+                "花了五元。",
+                vec!["花了/CN_WORD@0..2", "五元/MONEY=CNY 5@2..4"],
+            ),
+            (
+                "12元一张",
+                vec!["12元/MONEY=CNY 12@0..3", "一张/CN_WORD@3..5"],
+            ),
+            (
+                "3元素周期表",
+                vec!["3/ARABIC@0..1", "元素/CN_WORD@1..3", "周期表/CN_WORD@3..6"],
+            ),
+            ("1,000.25", vec!["1,000.25/DECIMAL=1000.25@0..8"]),
+            (
+                "第三名买了3-5个苹果",
+                vec![
+                    "第三名/ORDINAL=3@0..3",
+                    "买了/CN_WORD@3..5",
+                    "3-5个/RANGE=3-5@5..9",
+                    "苹果/CN_WORD@9..11",
+                ],
+            ),
+            (
+                "十几岁，三到五天，两三个",
+                vec![
+                    "十几岁/RANGE=11-19@0..3",
+                    "三到五天/RANGE=3-5@4..8",
+                    "两三个/RANGE=2-3@9..12",
+                ],
+            ),
+            // a score, not a range
+            ("3-5", vec!["3-5/LETTER@0..3"]),
+            // offsets still refer to the original chars
+            (
+                "Café naïve Müller ΟΔΥΣΣΕΥΣ Москва cafe\u{301}",
+                vec![
+                    "café/ENGLISH@0..4",
+                    "naïve/ENGLISH@5..10",
+                    "müller/ENGLISH@11..17",
+                    "οδυσσευσ/ENGLISH@18..26",
+                    "москва/ENGLISH@27..33",
+                    "cafe\u{301}/ENGLISH@34..39",
+                ],
+            ),
+            (
+                "안녕하세요 세계 コンピューター・ゲーム ｶﾞｿﾘﾝ",
+                vec![
+                    "안녕하세요/OtherCjk@0..5",
+                    "세계/OtherCjk@6..8",
+                    "コンピューター/OtherCjk@9..16",
+                    "・/OtherCjk@16..17",
+                    "ゲーム/OtherCjk@17..20",
+                    "ガソリン/OtherCjk@21..26",
+                ],
+            ),
+            (
+                "你好，世界！",
+                vec!["你好/CN_WORD@0..2", "世界/CN_WORD@3..5"],
+            ),
+            (
+                "他来到了网易杭研大厦",
+                vec![
+                    "他/CN_WORD@0..1",
+                    "来/CN_CHAR@1..2",
+                    "到了/CN_WORD@2..4",
+                    "网易/CN_WORD@4..6",
+                    "杭/CN_CHAR@6..7",
+                    "研/CN_CHAR@7..8",
+                    "大厦/CN_WORD@8..10",
+                ],
+            ),
+            ("The cats", vec!["the/ENGLISH@0..3", "cats/ENGLISH@4..8"]),
+        ];
+        let ik = IKSegmenter::new();
+        for (text, expected) in cases {
+            assert_eq!(
+                expected,
+                describe(&ik.tokenize(text, TokenMode::SEARCH)),
+                "{}",
+                text
+            );
+        }
+
+        let tokens = ik.tokenize("二三十个人", TokenMode::INDEX);
+        assert_eq!("二三十个", tokens[0].lexeme_text());
+        assert_eq!(Some("20-30"), tokens[0].value());
+    }
+
+    #[test]
+    fn test_builder_options() {
+        let builder = IKSegmenter::builder();
+        let cases = vec![
+            (
+                builder.clone().emit_arabic_number(true),
+                "二〇二三年",
+                vec![
+                    "二〇二三/TYPE_CNUM=2023@0..4",
+                    "2023/ARABIC@0..4",
+                    "年/CN_CHAR@4..5",
+                ],
+            ),
+            (
+                builder.clone().contact_sub_tokens(true),
+                "zhiyi.shen@gmail.com",
+                vec![
+                    "zhiyi.shen@gmail.com/EMAIL@0..20",
+                    "zhiyi.shen/LETTER@0..10",
+                    "gmail.com/LETTER@11..20",
+                ],
+            ),
+            (
+                builder.clone().recognize_oov(true),
+                "他来到了网易杭研大厦",
+                vec![
+                    "他/CN_WORD@0..1",
+                    "来/CN_CHAR@1..2",
+                    "到了/CN_WORD@2..4",
+                    "网易/CN_WORD@4..6",
+                    "杭研/CN_OOV@6..8",
+                    "大厦/CN_WORD@8..10",
+                ],
+            ),
+            (
+                builder.clone().cjk_bigram(true),
+                "网易杭研大厦",
+                vec!["网易/CN_WORD@0..2", "杭研/BIGRAM@2..4", "大厦/CN_WORD@4..6"],
+            ),
+            (
+                builder.clone().cjk_bigram(true),
+                "すしを食べる",
+                vec![
+                    "すし/BIGRAM@0..2",
+                    "しを/BIGRAM@1..3",
+                    "を食/BIGRAM@2..4",
+                    "食べ/BIGRAM@3..5",
+                    "べる/BIGRAM@4..6",
+                ],
+            ),
+            (
+                builder.clone().emit_symbols(true),
+                "你好，世界！x=2 🇨🇳👨‍👩‍👧",
+                vec![
+                    "你好/CN_WORD@0..2",
+                    ",/PUNCTUATION@2..3",
+                    "世界/CN_WORD@3..5",
+                    "!/PUNCTUATION@5..6",
+                    "x/ENGLISH@6..7",
+                    "=/SYMBOL@7..8",
+                    "2/ARABIC@8..9",
+                    "🇨🇳/EMOJI@10..12",
+                    "👨‍👩‍👧/EMOJI@12..17",
+                ],
+            ),
+            (
+                builder.clone().emit_symbols(true),
+                "按1\u{FE0F}\u{20E3}键",
+                vec![
+                    "按/CN_CHAR@0..1",
+                    "1\u{FE0F}\u{20E3}/EMOJI@1..4",
+                    "键/CN_CHAR@4..5",
+                ],
+            ),
+            (
+                builder
+                    .clone()
+                    .stem_english(true)
+                    .remove_english_stop_words(true),
+                "The cats are running of 奔跑的猫",
+                vec![
+                    "cat/ENGLISH@4..8",
+                    "run/ENGLISH@13..20",
+                    "奔跑/CN_WORD@24..26",
+                    "的/CN_WORD@26..27",
+                    "猫/CN_CHAR@27..28",
+                ],
+            ),
+            (
+                builder
+                    .clone()
+                    .normalization(Normalization {
+                        form: NormalizationForm::NFKC,
+                        case_fold: true,
+                        strip_accents: true,
+                        ..Default::default()
+                    })
+                    .unwrap(),
+                "㈱ﬁnance Café",
+                vec![
+                    "株/CN_CHAR@0..1",
+                    "finance/ENGLISH@1..7",
+                    "cafe/ENGLISH@8..12",
+                ],
+            ),
+            (
+                builder
+                    .clone()
+                    .normalization(Normalization {
+                        traditional_to_simplified: true,
+                        ..GLOBAL_NORMALIZATION.clone()
+                    })
+                    .unwrap()
+                    .emit_original_script(true),
+                "我的電腦與資訊",
+                vec![
+                    "我/CN_WORD@0..1",
+                    "的/CN_WORD@1..2",
+                    "电脑/CN_WORD@2..4",
+                    "電腦/CN_WORD@2..4",
+                    "与/CN_CHAR@4..5",
+                    "與/CN_CHAR@4..5",
+                    "资讯/CN_WORD@5..7",
+                    "資訊/CN_WORD@5..7",
+                ],
+            ),
+        ];
+        for (builder, text, expected) in cases {
+            let tokens = builder.build().tokenize(text, TokenMode::SEARCH);
+            assert_eq!(expected, describe(&tokens), "{}", text);
+        }
+
+        // the dictionary words are case folded
+        let uncased = Normalization {
+            case_fold: false,
+            ..GLOBAL_NORMALIZATION.clone()
+        };
+        assert!(IKSegmenter::builder().normalization(uncased).is_err());
+    }
+
+    #[test]
+    fn test_word_delimiter() {
+        let cases = vec![
+            (
+                WordDelimiter::new(),
+                "GalaxyS24Ultra",
+                vec!["galaxys24ultra", "galaxy", "s", "24", "ultra"],
+            ),
+            (
+                WordDelimiter::new(),
+                "XMLHttp",
+                vec!["xmlhttp", "xml", "http"],
+            ),
+            (
+                WordDelimiter::new()
+                    .catenate_all(true)
+                    .preserve_original(false),
+                "wi-fi",
+                vec!["wi", "fi", "wifi"],
+            ),
+            (
+                WordDelimiter::new().split_on_case_change(false),
+                "PowerShot SD500",
+                vec!["powershot", "sd500", "sd", "500"],
+            ),
+        ];
+        for (word_delimiter, text, expected) in cases {
+            let ik = IKSegmenter::builder()
+                .word_delimiter(word_delimiter)
+                .build();
+            let tokens = ik.tokenize(text, TokenMode::SEARCH);
+            let token_texts: Vec<&str> = tokens.iter().map(|t| t.lexeme_text()).collect();
+            assert_eq!(expected, token_texts, "{}", text);
+        }
+    }
+
+    // the words are only added here, and no other test text contains them
+    fn add_words(words: Vec<&str>) {
+        cfg_if::cfg_if! {
+            if #[cfg(feature="use-parking-lot")] {GLOBAL_DICT.write().add_words(words)}
+            else /*if #[cfg(feature="use-std-sync")]*/ {GLOBAL_DICT.write().unwrap().add_words(words)}
+        }
+    }
+
+    #[test]
+    fn test_dict_words() {
+        add_words(vec![
+            "c++",
+            "c#",
+            ".net",
+            "node.js",
+            "at&t",
+            "new york",
+            "machine learning",
+        ]);
+        let ik = IKSegmenter::new();
+        let tokens = ik.tokenize("用C++和C#写.NET，也用Node.js连AT&T", TokenMode::SEARCH);
+        let words: Vec<String> = describe(&tokens)
+            .into_iter()
+            .filter(|t| t.is_ascii())
+            .collect();
+        assert_eq!(
+            vec![
+                "c++/CN_WORD@1..4",
+                "c#/CN_WORD@5..7",
+                ".net/CN_WORD@8..12",
+                "node.js/CN_WORD@15..22",
+                "at&t/CN_WORD@23..27",
+            ],
+            words
+        );
+
+        let text = "在New   York学Machine Learning";
+        assert_eq!(
+            vec![
+                "在/CN_CHAR@0..1",
+                "new york/PHRASE@1..11",
+                "学/CN_CHAR@11..12",
+                "machine learning/PHRASE@12..28",
+            ],
+            describe(&ik.tokenize(text, TokenMode::SEARCH))
+        );
+        let index = describe(&ik.tokenize(text, TokenMode::INDEX));
+        assert!(index.contains(&"new york/PHRASE@1..11".to_string()));
+        assert!(index.contains(&"york/ENGLISH@7..11".to_string()));
+        let tokens = ik.tokenize("renew yorkshire", TokenMode::INDEX);
+        assert!(tokens.iter().all(|t| t.lexeme_text() != "new york"));
+
+        // a dictionary word is not split by the word delimiter
+        let ik = IKSegmenter::builder()
+            .word_delimiter(WordDelimiter::new())
+            .build();
+        let tokens = ik.tokenize("Node.js", TokenMode::SEARCH);
+        let token_texts: Vec<&str> = tokens.iter().map(|t| t.lexeme_text()).collect();
+        assert_eq!(vec!["node.js", "node", "js"], token_texts);
+    }
+
     #[test]
     fn test_thread_safe() {
         let ik = IKSegmenter::new();
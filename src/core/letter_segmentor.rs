@@ -1,4 +1,4 @@
-use crate::core::char_util::utf8_len;
+use crate::core::char_util::{utf8_len, utf8_slice};
 use crate::core::char_util::CharType;
 use crate::core::lexeme::{Lexeme, LexemeType};
 use crate::core::ordered_linked_list::OrderedLinkedList;
@@ -158,8 +158,8 @@ impl LetterSegmenter {
                 }
                 CharType::USELESS if self.is_num_connector(&curr_char) => {}
                 _ => {
-                    let new_lexeme =
-                        Lexeme::new(start..(self.arabic_end.unwrap() + 1), LexemeType::ARABIC);
+                    let end = self.arabic_end.unwrap() + 1;
+                    let new_lexeme = Lexeme::new(start..end, self.arabic_type(input, start, end));
                     origin_lexemes.insert(new_lexeme);
                     self.reset_arabic_state();
                 }
@@ -168,12 +168,22 @@ impl LetterSegmenter {
         let char_count = utf8_len(input);
         self.arabic_end.zip(self.arabic_start).map(|(end, start)| {
             if end == (char_count - 1) {
-                let new_lexeme = Lexeme::new(start..end + 1, LexemeType::ARABIC);
+                let lexeme_type = self.arabic_type(input, start, end + 1);
+                let new_lexeme = Lexeme::new(start..end + 1, lexeme_type);
                 origin_lexemes.insert(new_lexeme);
                 self.reset_arabic_state();
             }
         });
     }
+    // DECIMAL for a number with a single decimal point, like "3.5" or "1,000.25"
+    fn arabic_type(&self, input: &str, start: usize, end: usize) -> LexemeType {
+        if utf8_slice(input, start, end).matches('.').count() == 1 {
+            LexemeType::DECIMAL
+        } else {
+            LexemeType::ARABIC
+        }
+    }
+
     fn reset_mix_state(&mut self) {
         self.start = None;
         self.end = None;
@@ -10,10 +10,28 @@ pub enum LexemeType {
     LETTER,
     CNWORD,
     CNCHAR,
+    CNOOV,
+    BIGRAM,
     OtherCJK,
     CNUM,
     COUNT,
     CQUAN,
+    DATE,
+    TIME,
+    EMAIL,
+    URL,
+    IP,
+    PHONE,
+    DECIMAL,
+    PERCENT,
+    MONEY,
+    ORDINAL,
+    RANGE,
+    PUNCTUATION,
+    SYMBOL,
+    EMOJI,
+    PINYIN,
+    PHRASE,
 }
 
 impl LexemeType {
@@ -24,10 +42,28 @@ impl LexemeType {
             LexemeType::LETTER => "LETTER",
             LexemeType::CNWORD => "CN_WORD",
             LexemeType::CNCHAR => "CN_CHAR",
+            LexemeType::CNOOV => "CN_OOV",
+            LexemeType::BIGRAM => "BIGRAM",
             LexemeType::OtherCJK => "OtherCjk",
             LexemeType::COUNT => "COUNT",
             LexemeType::CNUM => "TYPE_CNUM",
             LexemeType::CQUAN => "TYPE_CQUAN",
+            LexemeType::DATE => "DATE",
+            LexemeType::TIME => "TIME",
+            LexemeType::EMAIL => "EMAIL",
+            LexemeType::URL => "URL",
+            LexemeType::IP => "IP",
+            LexemeType::PHONE => "PHONE",
+            LexemeType::DECIMAL => "DECIMAL",
+            LexemeType::PERCENT => "PERCENT",
+            LexemeType::MONEY => "MONEY",
+            LexemeType::ORDINAL => "ORDINAL",
+            LexemeType::RANGE => "RANGE",
+            LexemeType::PUNCTUATION => "PUNCTUATION",
+            LexemeType::SYMBOL => "SYMBOL",
+            LexemeType::EMOJI => "EMOJI",
+            LexemeType::PINYIN => "PINYIN",
+            LexemeType::PHRASE => "PHRASE",
             _ => "UNKNOW",
         }
     }
@@ -39,6 +75,7 @@ pub struct Lexeme {
     pos: Range<usize>,
     lexeme_text: String,
     lexeme_type: LexemeType,
+    value: Option<String>,
 }
 
 impl Clone for Lexeme {
@@ -48,6 +85,7 @@ impl Clone for Lexeme {
             pos: self.pos.clone(),
             lexeme_text: self.lexeme_text.clone(),
             lexeme_type: self.lexeme_type.clone(),
+            value: self.value.clone(),
         }
     }
 }
@@ -83,6 +121,7 @@ impl Lexeme {
             pos,
             lexeme_type,
             lexeme_text: String::from(""),
+            value: None,
         }
     }
 
@@ -98,6 +137,11 @@ impl Lexeme {
         self.offset + self.pos.end
     }
 
+    pub fn set_pos(&mut self, pos: Range<usize>) {
+        self.offset = 0;
+        self.pos = pos;
+    }
+
     pub fn len(&self) -> usize {
         self.pos.len()
     }
@@ -106,9 +150,28 @@ impl Lexeme {
         &self.lexeme_text
     }
 
+    pub fn set_lexeme_text(&mut self, text: &str) {
+        self.lexeme_text = text.to_string();
+    }
+
+    /// normalized value of a number, DATE, TIME, MONEY, PERCENT, ORDINAL or
+    /// RANGE lexeme, like "960" for the CNUM "九百六十", "2023-10-01" for a
+    /// DATE, "CNY 5" for MONEY or "3-5" for a RANGE, None for other lexemes
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    pub fn set_value(&mut self, value: Option<String>) {
+        self.value = value;
+    }
+
     pub fn parse_lexeme_text(&mut self, input: &str) {
         let sub_text = utf8_slice(input, self.begin_pos(), self.end_pos());
-        self.lexeme_text = sub_text.to_string();
+        self.lexeme_text = match self.lexeme_type {
+            // the words of a phrase are separated by a single space
+            LexemeType::PHRASE => sub_text.split_whitespace().collect::<Vec<_>>().join(" "),
+            _ => sub_text.to_string(),
+        };
     }
 
     pub fn append(&mut self, l: &Lexeme, lexeme_type: LexemeType) -> bool {
@@ -2,6 +2,7 @@ use crate::core::lexeme::Lexeme;
 use crate::core::ordered_linked_list::{Link, OrderedLinkedList};
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 
 pub struct LexemePath {
     begin: i32,
@@ -104,13 +105,10 @@ impl LexemePath {
     }
 
     pub fn x_weight(&self) -> usize {
-        (self
-            .lexeme_list
+        // saturate on long paths instead of overflow
+        self.lexeme_list
             .iter()
-            .map(|l| l.len())
-            .collect::<Vec<usize>>())
-        .iter()
-        .product()
+            .fold(1usize, |product, l| product.saturating_mul(l.len()))
     }
 
     pub fn p_weight(&self) -> usize {
@@ -135,6 +133,26 @@ impl LexemePath {
     pub fn head_node(&self) -> Option<&Link<Lexeme>> {
         self.lexeme_list.head_node()
     }
+
+    pub fn score(&self) -> PathScore {
+        PathScore {
+            payload_len: self.payload_len(),
+            lexeme_count: self.len(),
+            path_len: self.path_len(),
+            x_weight: self.x_weight(),
+            p_weight: self.p_weight(),
+        }
+    }
+}
+
+/// the weights which LexemePath is ordered by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathScore {
+    pub payload_len: usize,
+    pub lexeme_count: usize,
+    pub path_len: usize,
+    pub x_weight: usize,
+    pub p_weight: usize,
 }
 
 impl Display for LexemePath {
@@ -229,3 +247,14 @@ impl PartialEq for LexemePath {
         };
     }
 }
+
+impl Hash for LexemePath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.begin().hash(state);
+        self.end().hash(state);
+        for lexeme in self.lexeme_list.iter() {
+            lexeme.begin_pos().hash(state);
+            lexeme.end_pos().hash(state);
+        }
+    }
+}
@@ -1,10 +1,20 @@
 pub(crate) mod char_util;
 pub(crate) mod cjk_segmenter;
 pub(crate) mod cn_quantifier_segmenter;
+pub(crate) mod contact_segmenter;
+pub(crate) mod date_segmenter;
+pub mod explanation;
+pub(crate) mod hmm;
 pub(crate) mod ik_arbitrator;
 pub mod ik_segmenter;
 pub(crate) mod letter_segmentor;
 pub(crate) mod lexeme;
 pub(crate) mod lexeme_path;
+pub mod normalizer;
+pub(crate) mod number_util;
 pub(crate) mod ordered_linked_list;
+pub(crate) mod other_cjk_segmenter;
+pub mod pinyin;
 pub mod segmentor;
+pub(crate) mod t2s;
+pub mod word_delimiter;
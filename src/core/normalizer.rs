@@ -0,0 +1,292 @@
+use crate::config::configuration::Configuration;
+use crate::config::default_config::DefaultConfig;
+use crate::core::char_util::{case_fold, fold_width, is_combining_diacritic, utf8_slice};
+use crate::core::lexeme::Lexeme;
+use crate::core::t2s::GLOBAL_T2S;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use unicode_normalization::char::{canonical_combining_class, decompose_compatible};
+use unicode_normalization::UnicodeNormalization;
+
+/// the normalization of ik.yml, read once
+pub(crate) static GLOBAL_NORMALIZATION: Lazy<Normalization> =
+    Lazy::new(|| DefaultConfig::new().get_normalization());
+
+/// unicode normalization applied to the text before segmentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NormalizationForm {
+    /// fullwidth forms to halfwidth and halfwidth katakana to fullwidth,
+    /// one char to one char
+    Width,
+    NFKC,
+    NFKD,
+}
+
+/// how the text is normalized before segmentation, the `normalization`
+/// section of ik.yml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Normalization {
+    pub form: NormalizationForm,
+    /// simple case folding for the `Width` form, full unicode case folding
+    /// for the others
+    pub case_fold: bool,
+    /// remove diacritics of latin, greek and cyrillic letters, "café" -> "cafe"
+    pub strip_accents: bool,
+    /// convert traditional chinese to simplified by the t2s dicts of ik.yml,
+    /// like "資訊" -> "资讯"
+    pub traditional_to_simplified: bool,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Normalization {
+            form: NormalizationForm::Width,
+            case_fold: true,
+            strip_accents: false,
+            traditional_to_simplified: false,
+        }
+    }
+}
+
+/// a normalization of the text the words of the dictionary, normalized by
+/// the `normalization` section of ik.yml, would not match
+#[derive(Debug, Clone)]
+pub struct IncompatibleNormalizationError;
+
+impl Display for IncompatibleNormalizationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "normalization incompatible with the dictionary")
+    }
+}
+
+impl Error for IncompatibleNormalizationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+/// normalized text, with the chars of the original text each of its chars
+/// was normalized from
+#[derive(Debug)]
+pub(crate) struct NormalizedText {
+    pub text: String,
+    // the text before the traditional to simplified conversion, if changed
+    unconverted: Option<String>,
+    origins: Vec<Range<usize>>,
+    // every char normalized from the char at the same position
+    aligned: bool,
+    // chars of the original text
+    original: Vec<char>,
+}
+
+impl NormalizedText {
+    /// the chars at the position before they were converted to simplified
+    /// chinese, if any of them was
+    pub fn unconverted_text(&self, begin: usize, end: usize) -> Option<&str> {
+        let unconverted = self.unconverted.as_ref()?;
+        let text = utf8_slice(unconverted, begin, end);
+        if text == utf8_slice(&self.text, begin, end) {
+            None
+        } else {
+            Some(text)
+        }
+    }
+
+    /// whether the char at the position was uppercase before normalization
+    pub fn was_uppercase(&self, pos: usize) -> bool {
+        self.origins
+            .get(pos)
+            .and_then(|origin| self.original.get(origin.start))
+            .is_some_and(|c| c.is_uppercase())
+    }
+
+    /// move the lexemes from positions in the normalized text to positions
+    /// in the original text
+    pub fn restore_positions(&self, lexemes: &mut [Lexeme]) {
+        if self.aligned {
+            return;
+        }
+        for lexeme in lexemes.iter_mut() {
+            let (begin, end) = (lexeme.begin_pos(), lexeme.end_pos());
+            if begin < end && end <= self.origins.len() {
+                lexeme.set_pos(self.origins[begin].start..self.origins[end - 1].end);
+            }
+        }
+    }
+}
+
+impl Normalization {
+    pub(crate) fn normalize(&self, input: &str) -> NormalizedText {
+        let mut text = String::with_capacity(input.len());
+        let mut origins = Vec::with_capacity(input.len());
+        for (origin, cluster) in self.clusters(input) {
+            for c in self.normalize_cluster(cluster).chars() {
+                text.push(c);
+                origins.push(origin.clone());
+            }
+        }
+        let mut unconverted = None;
+        if self.traditional_to_simplified {
+            let converted = GLOBAL_T2S.convert(&text);
+            if converted != text {
+                unconverted = Some(std::mem::replace(&mut text, converted));
+            }
+        }
+        let aligned = origins
+            .iter()
+            .enumerate()
+            .all(|(i, origin)| *origin == (i..i + 1));
+        NormalizedText {
+            text,
+            unconverted,
+            origins,
+            aligned,
+            original: input.chars().collect(),
+        }
+    }
+
+    /// whether this normalization does all the other one does, so the words
+    /// normalized by the other one are normalized the same by this one, the
+    /// traditional to simplified conversion aside
+    pub(crate) fn covers(&self, other: &Normalization) -> bool {
+        (self.form == other.form || other.form == NormalizationForm::Width)
+            && (self.case_fold || !other.case_fold)
+            && (self.strip_accents || !other.strip_accents)
+    }
+
+    /// the normalized text alone, like a dictionary word
+    pub(crate) fn normalize_word(&self, word: &str) -> String {
+        let text: String = self
+            .clusters(word)
+            .into_iter()
+            .map(|(_, cluster)| self.normalize_cluster(cluster))
+            .collect();
+        if self.traditional_to_simplified {
+            GLOBAL_T2S.convert(&text)
+        } else {
+            text
+        }
+    }
+
+    // the text split into clusters normalized on their own, a starter and
+    // the chars combining with it, or single chars for the `Width` form but
+    // for a halfwidth kana and its voiced sound mark
+    fn clusters<'a>(&self, input: &'a str) -> Vec<(Range<usize>, &'a str)> {
+        let mut clusters: Vec<(Range<usize>, &'a str)> = Vec::new();
+        let mut start = (0, 0);
+        for (pos, (byte_pos, c)) in input.char_indices().enumerate() {
+            let starter = match self.form {
+                // "ｶﾞ" is composed to "ガ"
+                NormalizationForm::Width => !matches!(c, '\u{FF9E}' | '\u{FF9F}'),
+                _ => is_starter(c),
+            };
+            if pos > start.0 && starter {
+                clusters.push((start.0..pos, &input[start.1..byte_pos]));
+                start = (pos, byte_pos);
+            }
+        }
+        let char_count = start.0 + input[start.1..].chars().count();
+        if char_count > start.0 {
+            clusters.push((start.0..char_count, &input[start.1..]));
+        }
+        clusters
+    }
+
+    fn normalize_cluster(&self, cluster: &str) -> String {
+        let mut normalized: String = match self.form {
+            NormalizationForm::Width if cluster.chars().nth(1).is_some() => {
+                cluster.chars().map(fold_width).nfc().collect()
+            }
+            NormalizationForm::Width => cluster.chars().map(fold_width).collect(),
+            NormalizationForm::NFKC => cluster.nfkc().collect(),
+            NormalizationForm::NFKD => cluster.nfkd().collect(),
+        };
+        if self.strip_accents {
+            let stripped = normalized.nfd().filter(|c| !is_combining_diacritic(*c));
+            normalized = match self.form {
+                NormalizationForm::NFKD => stripped.collect(),
+                _ => stripped.nfc().collect(),
+            };
+        }
+        if self.case_fold {
+            normalized = match self.form {
+                NormalizationForm::Width => normalized.chars().map(case_fold).collect(),
+                NormalizationForm::NFKC => caseless::default_case_fold_str(&normalized)
+                    .nfkc()
+                    .collect(),
+                NormalizationForm::NFKD => caseless::default_case_fold_str(&normalized)
+                    .nfkd()
+                    .collect(),
+            };
+        }
+        normalized
+    }
+}
+
+// a char which does not compose with the chars before it
+fn is_starter(c: char) -> bool {
+    let mut first = None;
+    decompose_compatible(c, |d| {
+        first.get_or_insert(d);
+    });
+    first.map_or(true, |d| canonical_combining_class(d) == 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn normalize(normalization: &Normalization, input: &str) -> (String, Vec<Range<usize>>) {
+        let normalized = normalization.normalize(input);
+        (normalized.text, normalized.origins)
+    }
+
+    #[test]
+    fn test_normalize() {
+        let width = Normalization::default();
+        assert_eq!("abc 1", width.normalize("ＡＢＣ　１").text);
+        assert!(width.normalize("ＡＢＣ　１").aligned);
+        let (text, origins) = normalize(&width, "ｶﾞｿﾘﾝ ﾎﾟﾝﾌﾟ");
+        assert_eq!("ガソリン ポンプ", text);
+        assert_eq!(vec![0..2, 2..3, 3..4, 4..5], origins[..4]);
+        assert_eq!(6..8, origins[5]);
+
+        let nfkc = Normalization {
+            form: NormalizationForm::NFKC,
+            ..Default::default()
+        };
+        // "ﬁ" becomes two chars, "ｶﾞ" one char
+        let (text, origins) = normalize(&nfkc, "ﬁx ｶﾞス ①");
+        assert_eq!("fix ガス 1", text);
+        assert_eq!(
+            vec![0..1, 0..1, 1..2, 2..3, 3..5, 5..6, 6..7, 7..8],
+            origins
+        );
+        assert_eq!("strasse", nfkc.normalize("Straße").text);
+
+        let stripped = Normalization {
+            form: NormalizationForm::NFKC,
+            strip_accents: true,
+            ..Default::default()
+        };
+        let (text, origins) = normalize(&stripped, "Cafe\u{301} naïve ガ");
+        assert_eq!("cafe naive ガ", text);
+        assert_eq!(0..1, origins[0]);
+        assert_eq!(3..5, origins[3]);
+        assert_eq!(5..6, origins[4]);
+
+        let nfkd = Normalization {
+            form: NormalizationForm::NFKD,
+            case_fold: false,
+            ..Default::default()
+        };
+        let (text, origins) = normalize(&nfkd, "É");
+        assert_eq!("E\u{301}", text);
+        assert_eq!(vec![0..1, 0..1], origins);
+    }
+}
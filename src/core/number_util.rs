@@ -0,0 +1,225 @@
+/// value of a chinese number digit or an arabic digit
+pub fn digit_value(c: char) -> Option<u64> {
+    match c {
+        '零' | '〇' => Some(0),
+        '一' | '壹' => Some(1),
+        '二' | '两' | '贰' => Some(2),
+        '三' | '叁' => Some(3),
+        '四' | '肆' => Some(4),
+        '五' | '伍' => Some(5),
+        '六' | '陆' => Some(6),
+        '七' | '柒' => Some(7),
+        '八' | '捌' => Some(8),
+        '九' | '玖' => Some(9),
+        _ => c.to_digit(10).map(u64::from),
+    }
+}
+
+// value of a unit below 万
+fn small_unit_value(c: char) -> Option<u64> {
+    match c {
+        '十' | '拾' => Some(10),
+        '百' | '佰' => Some(100),
+        '千' | '仟' => Some(1000),
+        _ => None,
+    }
+}
+
+// value of a unit grouping everything before it
+fn big_unit_value(c: char) -> Option<u64> {
+    match c {
+        '万' | '萬' => Some(10_000),
+        '亿' | '億' => Some(100_000_000),
+        '兆' => Some(1_000_000_000_000),
+        _ => None,
+    }
+}
+
+/// parse a number written in chinese numerals, arabic digits or a mix of
+/// both, like "九百六十", "十八", "二〇二三", "廿三", "一百五" or "3万"
+pub fn parse_number(text: &str) -> Option<u64> {
+    let chars: Vec<char> = text.chars().filter(|c| *c != ',').collect();
+    if chars.is_empty() {
+        return None;
+    }
+    let has_unit = chars.iter().any(|c| {
+        small_unit_value(*c).is_some()
+            || big_unit_value(*c).is_some()
+            || matches!(c, '廿' | '卅' | '卌')
+    });
+    if !has_unit {
+        // digit by digit, like a year
+        let mut value = 0u64;
+        for c in chars {
+            value = value.checked_mul(10)?.checked_add(digit_value(c)?)?;
+        }
+        return Some(value);
+    }
+
+    // completed groups of 亿/兆, the current 万 group and the part below 万
+    let mut total = 0u64;
+    let mut wan = 0u64;
+    let mut small = 0u64;
+    let mut digit: Option<u64> = None;
+    let mut last_unit = 1u64;
+    let mut after_unit = false;
+    // a nonzero digit came before the last unit, unlike the "万" of "万一"
+    let mut unit_counted = false;
+    let mut prev_arabic = false;
+    for c in chars {
+        let arabic = c.is_ascii_digit();
+        if let Some(d) = digit_value(c) {
+            digit = match digit {
+                Some(v) if arabic && prev_arabic => Some(v.checked_mul(10)?.checked_add(d)?),
+                // "二三十" is a range, not a number
+                Some(v) if v != 0 => return None,
+                _ => Some(d),
+            };
+            if d == 0 && !arabic {
+                after_unit = false;
+            }
+        } else if let Some(unit) = small_unit_value(c) {
+            small = small.checked_add(digit.unwrap_or(1).checked_mul(unit)?)?;
+            unit_counted = digit.is_some_and(|d| d != 0);
+            digit = None;
+            last_unit = unit;
+            after_unit = true;
+        } else if matches!(c, '廿' | '卅' | '卌') {
+            let tens = match c {
+                '廿' => 20,
+                '卅' => 30,
+                _ => 40,
+            };
+            small = small.checked_add(tens)?;
+            digit = None;
+            last_unit = 10;
+            after_unit = true;
+        } else if let Some(unit) = big_unit_value(c) {
+            let mut group = small.checked_add(digit.unwrap_or(0))?;
+            unit_counted = group != 0;
+            if unit == 10_000 {
+                if group == 0 {
+                    group = 1;
+                }
+                wan = wan.checked_add(group.checked_mul(unit)?)?;
+            } else {
+                group = group.checked_add(wan)?;
+                if group == 0 && total == 0 {
+                    group = 1;
+                }
+                // 兆 also multiplies the 亿 groups before it
+                if unit > 100_000_000 {
+                    group = group.checked_add(total)?;
+                    total = 0;
+                }
+                total = total.checked_add(group.checked_mul(unit)?)?;
+                wan = 0;
+            }
+            small = 0;
+            digit = None;
+            last_unit = unit;
+            after_unit = true;
+        } else {
+            return None;
+        }
+        prev_arabic = arabic;
+    }
+    if let Some(d) = digit {
+        // "一百五" is 150 and "三万五" is 35000, but "一百零五" is 105 and
+        // "万一" is 10001
+        if after_unit && unit_counted && last_unit >= 100 && d < 10 {
+            small = small.checked_add(d.checked_mul(last_unit / 10)?)?;
+        } else {
+            small = small.checked_add(d)?;
+        }
+    }
+    total.checked_add(wan)?.checked_add(small)
+}
+
+/// parse a number which may have a fraction, like "3.5", "1,000.25",
+/// "3.5亿" or "三点五", into its plain decimal form like "350000000"
+pub fn parse_decimal(text: &str) -> Option<String> {
+    let text: String = text.chars().filter(|c| *c != ',').collect();
+    let Some(point) = text.find(|c| c == '.' || c == '点') else {
+        return parse_number(&text).map(|value| value.to_string());
+    };
+    let integer = parse_number(&text[..point])?;
+    let rest: Vec<char> = text[point..].chars().skip(1).collect();
+    let scale = rest
+        .iter()
+        .take_while(|c| digit_value(**c).is_some())
+        .count();
+    if scale == 0 {
+        return None;
+    }
+    let mut mantissa = u128::from(integer);
+    for c in rest[..scale].iter() {
+        mantissa = mantissa
+            .checked_mul(10)?
+            .checked_add(u128::from(digit_value(*c)?))?;
+    }
+    // only units may follow the fraction, like the "亿" of "3.5亿"
+    let units: String = rest[scale..].iter().collect();
+    if !units.is_empty() {
+        if units.chars().any(|c| digit_value(c).is_some()) {
+            return None;
+        }
+        mantissa = mantissa.checked_mul(u128::from(parse_number(&units)?))?;
+    }
+    Some(format_decimal(mantissa, scale))
+}
+
+// mantissa / 10^scale without trailing zeros
+fn format_decimal(mantissa: u128, scale: usize) -> String {
+    let digits = format!("{:0>width$}", mantissa, width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(Some(960), parse_number("九百六十"));
+        assert_eq!(Some(18), parse_number("十八"));
+        assert_eq!(Some(105), parse_number("一百零五"));
+        assert_eq!(Some(150), parse_number("一百五"));
+        assert_eq!(Some(35000), parse_number("三万五"));
+        assert_eq!(Some(2023), parse_number("二〇二三"));
+        assert_eq!(Some(2), parse_number("两"));
+        assert_eq!(Some(23), parse_number("廿三"));
+        assert_eq!(Some(30), parse_number("卅"));
+        assert_eq!(Some(30000), parse_number("3万"));
+        assert_eq!(Some(9600000), parse_number("960万"));
+        assert_eq!(Some(1000), parse_number("1,000"));
+        assert_eq!(Some(120_000_000), parse_number("一亿二千万"));
+        assert_eq!(Some(300_050_000), parse_number("三亿零五万"));
+        assert_eq!(Some(10_000), parse_number("万"));
+        assert_eq!(Some(10_001), parse_number("万一"));
+        assert_eq!(Some(1_001), parse_number("千一"));
+        assert_eq!(Some(1_300_000_000_000), parse_number("一兆三千亿"));
+        assert_eq!(None, parse_number("二三十"));
+        assert_eq!(None, parse_number("个"));
+        assert_eq!(None, parse_number(""));
+    }
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(Some("3.5".to_string()), parse_decimal("3.5"));
+        assert_eq!(Some("1000.25".to_string()), parse_decimal("1,000.25"));
+        assert_eq!(Some("350000000".to_string()), parse_decimal("3.5亿"));
+        assert_eq!(Some("0.05".to_string()), parse_decimal("0.05"));
+        assert_eq!(Some("2".to_string()), parse_decimal("2.0"));
+        assert_eq!(Some("3.5".to_string()), parse_decimal("三点五"));
+        assert_eq!(Some("960".to_string()), parse_decimal("九百六十"));
+        assert_eq!(None, parse_decimal("3."));
+        assert_eq!(None, parse_decimal("3.5.1"));
+    }
+}
@@ -0,0 +1,72 @@
+use crate::core::char_util::CharType;
+use crate::core::lexeme::{Lexeme, LexemeType};
+use crate::core::ordered_linked_list::OrderedLinkedList;
+use crate::core::segmentor::Segmenter;
+
+const SEGMENTER_NAME: &str = "OTHER_CJK_SEGMENTER";
+
+#[derive(PartialEq, Debug)]
+enum Script {
+    Hangul,
+    Katakana,
+}
+
+/// group a run of hangul syllables, like "안녕하세요", or of katakana, like
+/// "コンピューター", into one lexeme
+#[derive(Default, Debug)]
+pub struct OtherCjkSegmenter {
+    // end of the last run
+    end: usize,
+}
+
+impl Segmenter for OtherCjkSegmenter {
+    fn analyze(
+        &mut self,
+        input: &str,
+        cursor: usize,
+        curr_char_type: &CharType,
+        origin_lexemes: &mut OrderedLinkedList<Lexeme>,
+    ) {
+        if cursor < self.end {
+            return;
+        }
+        match curr_char_type {
+            CharType::OtherCjk => {}
+            _ => return,
+        }
+        let mut chars = input.chars().skip(cursor);
+        let Some(first) = chars.next() else {
+            return;
+        };
+        // a voiced sound mark does not start a word
+        let Some(script) = script(first).filter(|_| !is_sound_mark(first)) else {
+            return;
+        };
+        let len = 1 + chars
+            .take_while(|c| self::script(*c).as_ref() == Some(&script))
+            .count();
+        origin_lexemes.insert(Lexeme::new(cursor..cursor + len, LexemeType::OtherCJK));
+        self.end = cursor + len;
+    }
+
+    fn name(&self) -> &str {
+        SEGMENTER_NAME
+    }
+}
+
+fn is_sound_mark(c: char) -> bool {
+    matches!(c, '\u{3099}' | '\u{309A}')
+}
+
+fn script(c: char) -> Option<Script> {
+    match c {
+        '\u{AC00}'..='\u{D7A3}' => Some(Script::Hangul),
+        // the middle dot "・" separates words
+        '\u{30A1}'..='\u{30FA}' | '\u{30FC}'..='\u{30FE}' | '\u{31F0}'..='\u{31FF}' => {
+            Some(Script::Katakana)
+        }
+        // combining voiced sound marks, the regularized "ﾞ" and "ﾟ"
+        '\u{3099}' | '\u{309A}' => Some(Script::Katakana),
+        _ => None,
+    }
+}
@@ -0,0 +1,441 @@
+use crate::config::configuration::Configuration;
+use crate::config::default_config::DefaultConfig;
+use crate::core::lexeme::{Lexeme, LexemeType};
+use crate::dict::dictionary::GLOBAL_DICT;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::ops::Range;
+
+pub(crate) static GLOBAL_PINYIN: Lazy<PinyinTable> = Lazy::new(|| {
+    let cfg = DefaultConfig::new();
+    let mut table = PinyinTable::default();
+    for file_path in cfg.get_pinyin_dictionaries().iter() {
+        table.load(file_path);
+    }
+    table
+});
+
+/// pinyin of chinese chars, and of words whose polyphones are not read by
+/// the reading of the char
+#[derive(Default, Debug)]
+pub(crate) struct PinyinTable {
+    chars: HashMap<char, String>,
+    phrases: HashMap<String, Vec<String>>,
+    phrase_starts: HashSet<char>,
+    max_phrase_len: usize,
+    syllables: HashSet<String>,
+}
+
+impl PinyinTable {
+    /// load lines of a char or word and the pinyin of each of its chars
+    pub fn load(&mut self, file_path: &str) {
+        let file = File::open(file_path)
+            .unwrap_or_else(|e| panic!("open pinyin dict:{} error:{}", file_path, e));
+        for line in BufReader::new(file).lines() {
+            let line =
+                line.unwrap_or_else(|e| panic!("read pinyin dict:{} error:{}", file_path, e));
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let word = fields.next().unwrap();
+            let syllables: Vec<String> = fields.map(|s| s.to_string()).collect();
+            let len = word.chars().count();
+            if len != syllables.len() {
+                log::warn!("pinyin dict:{} invalid line:{}", file_path, line);
+                continue;
+            }
+            self.syllables.extend(syllables.iter().cloned());
+            let first = word.chars().next().unwrap();
+            if len == 1 {
+                self.chars.insert(first, syllables[0].clone());
+            } else {
+                self.phrases.insert(word.to_string(), syllables);
+                self.phrase_starts.insert(first);
+                self.max_phrase_len = self.max_phrase_len.max(len);
+            }
+        }
+        log::debug!(
+            "after load pinyin dict:{}, chars = {}, phrases = {}",
+            file_path,
+            self.chars.len(),
+            self.phrases.len()
+        );
+    }
+
+    /// pinyin of every char of the text, words of the phrase table first,
+    /// None for chars without pinyin
+    pub fn pinyin(&self, text: &str) -> Vec<Option<&str>> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut pinyin = Vec::with_capacity(chars.len());
+        let mut i = 0;
+        'chars: while i < chars.len() {
+            if self.phrase_starts.contains(&chars[i]) {
+                let max_len = self.max_phrase_len.min(chars.len() - i);
+                for len in (2..=max_len).rev() {
+                    let phrase: String = chars[i..i + len].iter().collect();
+                    if let Some(syllables) = self.phrases.get(&phrase) {
+                        pinyin.extend(syllables.iter().map(|s| Some(s.as_str())));
+                        i += len;
+                        continue 'chars;
+                    }
+                }
+            }
+            pinyin.push(self.chars.get(&chars[i]).map(|s| s.as_str()));
+            i += 1;
+        }
+        pinyin
+    }
+
+    pub fn is_syllable(&self, text: &str) -> bool {
+        self.syllables.contains(text)
+    }
+
+    fn char_pinyin(&self, c: char) -> Option<&str> {
+        self.chars.get(&c).map(|s| s.as_str())
+    }
+}
+
+/// split runs of latin letters typed as pinyin, like "zhonghuarenmin",
+/// "xi'an" or the initials "bjdx", into PINYIN lexemes of one syllable each,
+/// runs which are not pinyin stay ENGLISH lexemes
+#[derive(Default, Debug, Clone)]
+pub struct PinyinSegmenter {}
+
+impl PinyinSegmenter {
+    pub fn new() -> Self {
+        PinyinSegmenter::default()
+    }
+
+    pub fn segment(&self, text: &str) -> Vec<Lexeme> {
+        let chars: Vec<char> = text.chars().map(|c| c.to_ascii_lowercase()).collect();
+        let mut lexemes = Vec::new();
+        let mut start = 0;
+        while start < chars.len() {
+            if !chars[start].is_ascii_lowercase() {
+                start += 1;
+                continue;
+            }
+            let end = start
+                + chars[start..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_lowercase() || **c == '\'')
+                    .count();
+            match split_syllables(&chars[start..end]) {
+                Some(syllables) => {
+                    for syllable in syllables {
+                        let pos = start + syllable.start..start + syllable.end;
+                        let mut lexeme = Lexeme::new(pos.clone(), LexemeType::PINYIN);
+                        lexeme.set_lexeme_text(&chars[pos].iter().collect::<String>());
+                        lexemes.push(lexeme);
+                    }
+                }
+                None => {
+                    let mut lexeme = Lexeme::new(start..end, LexemeType::ENGLISH);
+                    lexeme.set_lexeme_text(&chars[start..end].iter().collect::<String>());
+                    lexemes.push(lexeme);
+                }
+            }
+            start = end;
+        }
+        lexemes
+    }
+
+    /// words of the main dict spelled by the syllables, a syllable may also
+    /// be the initial of the pinyin of a char, like "bjdx" for "北京大学"
+    pub fn candidate_words(&self, syllables: &[Lexeme]) -> Vec<String> {
+        let syllables: Vec<&str> = syllables
+            .iter()
+            .filter(|l| matches!(l.lexeme_type(), LexemeType::PINYIN))
+            .map(|l| l.lexeme_text())
+            .collect();
+        let table = &GLOBAL_PINYIN;
+        let accept = |depth: usize, c: char| {
+            table.char_pinyin(c).is_some_and(|p| {
+                let syllable = syllables[depth];
+                p == syllable || (INITIALS.contains(&syllable) && p.starts_with(syllable))
+            })
+        };
+        let lock_guard = {cfg_if::cfg_if! {
+            if #[cfg(feature="use-parking-lot")] {GLOBAL_DICT.read()}
+            else /*if #[cfg(feature="use-std-sync")]*/ {
+                match GLOBAL_DICT.read() {
+                    Err(_err) => return Vec::new(),
+                    Ok(lck) => lck
+                }
+            }
+        }};
+        let mut words = lock_guard.find_in_main_dict(syllables.len(), &accept);
+        // words with polyphones not read by the reading of the char
+        for (word, pinyin) in table.phrases.iter() {
+            if pinyin.len() == syllables.len()
+                && pinyin.iter().zip(syllables.iter()).all(|(p, s)| p == s)
+                && lock_guard
+                    .match_in_main_dict(word)
+                    .iter()
+                    .any(|hit| hit.is_match() && hit.len() == pinyin.len())
+                && !words.contains(word)
+            {
+                words.push(word.clone());
+            }
+        }
+        words.sort();
+        words
+    }
+}
+
+// INITIALS of the pinyin, standing for a syllable in abbreviations
+const INITIALS: [&str; 23] = [
+    "b", "p", "m", "f", "d", "t", "n", "l", "g", "k", "h", "j", "q", "x", "zh", "ch", "sh", "r",
+    "z", "c", "s", "y", "w",
+];
+
+const MAX_SYLLABLE_LEN: usize = 6;
+
+// the split of the chars into full syllables, like "zhong" "guo", or else
+// into initials, like "b" "j" "d" "x", a split mixing them, like "he" "l"
+// "lo" for "hello", is more likely an english word
+fn split_syllables(chars: &[char]) -> Option<Vec<Range<usize>>> {
+    let table = &GLOBAL_PINYIN;
+    split_pieces(chars, &|piece| table.is_syllable(piece))
+        .or_else(|| split_pieces(chars, &|piece| INITIALS.contains(&piece)))
+}
+
+// the split of the chars into the fewest accepted pieces, so "xian" is one
+// syllable and not "xi" "an", unless typed as "xi'an"
+fn split_pieces(chars: &[char], accept: &dyn Fn(&str) -> bool) -> Option<Vec<Range<usize>>> {
+    // pieces of the best split of chars[..i] and the start of its last piece
+    let mut best: Vec<Option<(usize, usize)>> = vec![None; chars.len() + 1];
+    best[0] = Some((0, 0));
+    for i in 0..chars.len() {
+        let Some((count, _)) = best[i] else {
+            continue;
+        };
+        if chars[i] == '\'' {
+            if best[i + 1].map_or(true, |(c, _)| count < c) {
+                best[i + 1] = Some((count, i));
+            }
+            continue;
+        }
+        for len in 1..=MAX_SYLLABLE_LEN.min(chars.len() - i) {
+            let piece: String = chars[i..i + len].iter().collect();
+            if !accept(&piece) {
+                continue;
+            }
+            if best[i + len].map_or(true, |(c, _)| count + 1 < c) {
+                best[i + len] = Some((count + 1, i));
+            }
+        }
+    }
+    best[chars.len()]?;
+    let mut pieces = Vec::new();
+    let mut end = chars.len();
+    while end > 0 {
+        let (_, start) = best[end]?;
+        if chars[start] != '\'' {
+            pieces.push(start..end);
+        }
+        end = start;
+    }
+    pieces.reverse();
+    Some(pieces)
+}
+
+/// add the pinyin of lexemes with chinese chars as PINYIN lexemes at the
+/// position of the lexeme, like "zhong" "guo", "zhongguo" and "zg" for "中国"
+#[derive(Debug, Clone)]
+pub struct PinyinFilter {
+    full: bool,
+    joined: bool,
+    initials: bool,
+}
+
+impl Default for PinyinFilter {
+    fn default() -> Self {
+        PinyinFilter {
+            full: true,
+            joined: true,
+            initials: true,
+        }
+    }
+}
+
+impl PinyinFilter {
+    pub fn new() -> Self {
+        PinyinFilter::default()
+    }
+
+    /// the pinyin of every char at the position of the char, like "zhong"
+    /// and "guo"
+    pub fn full(mut self, full: bool) -> Self {
+        self.full = full;
+        self
+    }
+
+    /// the pinyin of the lexeme, like "zhongguo"
+    pub fn joined(mut self, joined: bool) -> Self {
+        self.joined = joined;
+        self
+    }
+
+    /// the first letters of the pinyin of the lexeme, like "zg"
+    pub fn initials(mut self, initials: bool) -> Self {
+        self.initials = initials;
+        self
+    }
+
+    pub fn filter(&self, lexemes: Vec<Lexeme>) -> Vec<Lexeme> {
+        let mut results = Vec::with_capacity(lexemes.len() * 2);
+        // overlapping lexemes of the index mode share the pinyin of a char
+        let mut emitted = HashSet::new();
+        for lexeme in lexemes.into_iter() {
+            let pinyin = GLOBAL_PINYIN.pinyin(lexeme.lexeme_text());
+            let text_chars: Vec<char> = lexeme.lexeme_text().chars().collect();
+            let begin = lexeme.begin_pos();
+            let end = lexeme.end_pos();
+            results.push(lexeme);
+            if pinyin.iter().all(|p| p.is_none()) {
+                continue;
+            }
+            let mut add = |pos: usize, end: usize, text: String| {
+                if emitted.insert((pos, end, text.clone())) {
+                    let mut pinyin_lexeme = Lexeme::new(pos..end, LexemeType::PINYIN);
+                    pinyin_lexeme.set_lexeme_text(&text);
+                    results.push(pinyin_lexeme);
+                }
+            };
+            // the chars of a text changed in length by normalization, like
+            // "株式会社" of "㍿", have no positions of their own
+            if self.full && text_chars.len() == end - begin {
+                for (i, p) in pinyin.iter().enumerate() {
+                    if let Some(p) = p {
+                        add(begin + i, begin + i + 1, p.to_string());
+                    }
+                }
+            }
+            // chars without pinyin are kept, like the "3" of "3个"
+            if self.joined {
+                let joined = pinyin
+                    .iter()
+                    .zip(text_chars.iter())
+                    .map(|(p, c)| p.map_or(c.to_string(), |p| p.to_string()))
+                    .collect();
+                add(begin, end, joined);
+            }
+            if self.initials {
+                let initials = pinyin
+                    .iter()
+                    .zip(text_chars.iter())
+                    .map(|(p, c)| p.and_then(|p| p.chars().next()).unwrap_or(*c))
+                    .collect();
+                add(begin, end, initials);
+            }
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::ik_segmenter::{IKSegmenter, TokenMode};
+    use crate::core::normalizer::{Normalization, NormalizationForm, GLOBAL_NORMALIZATION};
+
+    #[test]
+    fn test_pinyin() {
+        let table = &GLOBAL_PINYIN;
+        assert_eq!(vec![Some("zhong"), Some("guo")], table.pinyin("中国"));
+        assert_eq!(vec![Some("yin"), Some("hang")], table.pinyin("银行"));
+        assert_eq!(vec![Some("xing"), Some("zou")], table.pinyin("行走"));
+        assert_eq!(vec![Some("chong"), Some("qing")], table.pinyin("重庆"));
+        assert_eq!(vec![Some("lv")], table.pinyin("绿"));
+        assert_eq!(vec![None, Some("ge")], table.pinyin("3个"));
+    }
+
+    #[test]
+    fn test_pinyin_filter() {
+        let ik = IKSegmenter::new();
+        let tokens = PinyinFilter::new().filter(ik.tokenize("中国银行", TokenMode::SEARCH));
+        let tokens: Vec<(&str, usize, usize)> = tokens
+            .iter()
+            .map(|t| (t.lexeme_text(), t.begin_pos(), t.end_pos()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("中国银行", 0, 4),
+                ("zhong", 0, 1),
+                ("guo", 1, 2),
+                ("yin", 2, 3),
+                ("hang", 3, 4),
+                ("zhongguoyinhang", 0, 4),
+                ("zgyh", 0, 4),
+            ],
+            tokens
+        );
+
+        let filter = PinyinFilter::new().full(false).joined(false);
+        let tokens = filter.filter(ik.tokenize("3个苹果", TokenMode::SEARCH));
+        let texts: Vec<&str> = tokens.iter().map(|t| t.lexeme_text()).collect();
+        assert_eq!(vec!["3个", "3g", "苹果", "pg"], texts);
+
+        let nfkc = IKSegmenter::builder()
+            .normalization(Normalization {
+                form: NormalizationForm::NFKC,
+                ..GLOBAL_NORMALIZATION.clone()
+            })
+            .unwrap()
+            .build();
+        let tokens = PinyinFilter::new().filter(nfkc.tokenize("㍿", TokenMode::SEARCH));
+        assert!(tokens.iter().all(|t| (t.begin_pos(), t.end_pos()) == (0, 1)));
+        assert!(tokens.iter().any(|t| t.lexeme_text() == "zhushihuishe"));
+    }
+
+    #[test]
+    fn test_pinyin_segmenter() {
+        let segmenter = PinyinSegmenter::new();
+        let texts = |lexemes: &[Lexeme]| -> Vec<String> {
+            lexemes
+                .iter()
+                .map(|l| l.lexeme_text().to_string())
+                .collect()
+        };
+        let lexemes = segmenter.segment("zhonghuarenmin");
+        assert_eq!(vec!["zhong", "hua", "ren", "min"], texts(&lexemes));
+        assert!(segmenter
+            .candidate_words(&lexemes)
+            .contains(&"中华人民".to_string()));
+
+        assert_eq!(vec!["xian"], texts(&segmenter.segment("xian")));
+        let lexemes = segmenter.segment("xi'an");
+        assert_eq!(vec!["xi", "an"], texts(&lexemes));
+        assert_eq!((3, 5), (lexemes[1].begin_pos(), lexemes[1].end_pos()));
+        assert!(segmenter
+            .candidate_words(&lexemes)
+            .contains(&"西安".to_string()));
+
+        let lexemes = segmenter.segment("bjdx");
+        assert_eq!(vec!["b", "j", "d", "x"], texts(&lexemes));
+        assert!(segmenter
+            .candidate_words(&lexemes)
+            .contains(&"北京大学".to_string()));
+
+        let lexemes = segmenter.segment("yinhang");
+        assert!(segmenter
+            .candidate_words(&lexemes)
+            .contains(&"银行".to_string()));
+
+        for text in ["VIP", "hello", "the", "string", "python", "google"] {
+            let lexemes = segmenter.segment(text);
+            assert_eq!(1, lexemes.len(), "{}", text);
+            assert_eq!(
+                LexemeType::ENGLISH.as_str(),
+                lexemes[0].lexeme_type().as_str(),
+                "{}",
+                text
+            );
+        }
+    }
+}
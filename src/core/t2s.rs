@@ -0,0 +1,106 @@
+use crate::config::configuration::Configuration;
+use crate::config::default_config::DefaultConfig;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+pub static GLOBAL_T2S: Lazy<T2sTable> = Lazy::new(|| {
+    let cfg = DefaultConfig::new();
+    let mut table = T2sTable::default();
+    for file_path in cfg.get_t2s_dictionaries().iter() {
+        table.load(file_path);
+    }
+    table
+});
+
+/// traditional to simplified chinese conversion, phrases first and then
+/// char by char, every char is converted to exactly one char
+#[derive(Default, Debug)]
+pub struct T2sTable {
+    chars: HashMap<char, char>,
+    phrases: HashMap<String, String>,
+    phrase_starts: HashSet<char>,
+    max_phrase_len: usize,
+}
+
+impl T2sTable {
+    /// load lines of a traditional char or phrase and its simplified form
+    pub fn load(&mut self, file_path: &str) {
+        let file = File::open(file_path)
+            .unwrap_or_else(|e| panic!("open t2s dict:{} error:{}", file_path, e));
+        for line in BufReader::new(file).lines() {
+            let line = line.unwrap_or_else(|e| panic!("read t2s dict:{} error:{}", file_path, e));
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let (Some(from), Some(to)) = (fields.next(), fields.next()) else {
+                log::warn!("t2s dict:{} invalid line:{}", file_path, line);
+                continue;
+            };
+            self.insert(from, to, file_path);
+        }
+        log::debug!(
+            "after load t2s dict:{}, chars = {}, phrases = {}",
+            file_path,
+            self.chars.len(),
+            self.phrases.len()
+        );
+    }
+
+    fn insert(&mut self, from: &str, to: &str, file_path: &str) {
+        let len = from.chars().count();
+        // offsets are kept only if the length is kept
+        if len != to.chars().count() {
+            log::warn!("t2s dict:{} length changed:{} {}", file_path, from, to);
+            return;
+        }
+        let first = from.chars().next().unwrap();
+        if len == 1 {
+            self.chars.insert(first, to.chars().next().unwrap());
+        } else {
+            self.phrases.insert(from.to_string(), to.to_string());
+            self.phrase_starts.insert(first);
+            self.max_phrase_len = self.max_phrase_len.max(len);
+        }
+    }
+
+    pub fn convert(&self, input: &str) -> String {
+        let chars: Vec<char> = input.chars().collect();
+        let mut converted = String::with_capacity(input.len());
+        let mut i = 0;
+        'chars: while i < chars.len() {
+            if self.phrase_starts.contains(&chars[i]) {
+                let max_len = self.max_phrase_len.min(chars.len() - i);
+                for len in (2..=max_len).rev() {
+                    let phrase: String = chars[i..i + len].iter().collect();
+                    if let Some(simplified) = self.phrases.get(&phrase) {
+                        converted.push_str(simplified);
+                        i += len;
+                        continue 'chars;
+                    }
+                }
+            }
+            converted.push(*self.chars.get(&chars[i]).unwrap_or(&chars[i]));
+            i += 1;
+        }
+        converted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_convert() {
+        let table = &GLOBAL_T2S;
+        assert_eq!("资讯与电脑", table.convert("資訊與電腦"));
+        assert_eq!("乾隆年间的头发", table.convert("乾隆年間的頭髮"));
+        assert_eq!("干净", table.convert("乾淨"));
+        assert_eq!("著名的反复", table.convert("著名的反覆"));
+        assert_eq!("abc 简体", table.convert("abc 简体"));
+    }
+}
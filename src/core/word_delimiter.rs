@@ -0,0 +1,174 @@
+use crate::core::char_util::{utf8_slice, CharType};
+use crate::core::lexeme::{Lexeme, LexemeType};
+use crate::core::normalizer::NormalizedText;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// split LETTER and ENGLISH lexemes, and dictionary words of letters like
+/// "wi-fi", into their parts, like "galaxy" "s" "24" for "GalaxyS24" or "wi"
+/// "fi" for "wi-fi"
+#[derive(Debug, Clone)]
+pub struct WordDelimiter {
+    split_on_case_change: bool,
+    split_on_numerics: bool,
+    catenate_all: bool,
+    preserve_original: bool,
+}
+
+impl Default for WordDelimiter {
+    fn default() -> Self {
+        WordDelimiter {
+            split_on_case_change: true,
+            split_on_numerics: true,
+            catenate_all: false,
+            preserve_original: true,
+        }
+    }
+}
+
+impl WordDelimiter {
+    pub fn new() -> Self {
+        WordDelimiter::default()
+    }
+
+    /// split where a lowercase letter is followed by an uppercase one of the
+    /// original text, "PowerShot" -> "power" "shot", "XMLHttp" -> "xml" "http"
+    pub fn split_on_case_change(mut self, split_on_case_change: bool) -> Self {
+        self.split_on_case_change = split_on_case_change;
+        self
+    }
+
+    /// split where letters and digits meet, "sd500" -> "sd" "500"
+    pub fn split_on_numerics(mut self, split_on_numerics: bool) -> Self {
+        self.split_on_numerics = split_on_numerics;
+        self
+    }
+
+    /// also emit all parts joined, "wi-fi" -> "wifi"
+    pub fn catenate_all(mut self, catenate_all: bool) -> Self {
+        self.catenate_all = catenate_all;
+        self
+    }
+
+    /// keep the lexeme which was split
+    pub fn preserve_original(mut self, preserve_original: bool) -> Self {
+        self.preserve_original = preserve_original;
+        self
+    }
+
+    pub(crate) fn process(&self, lexemes: Vec<Lexeme>, normalized: &NormalizedText) -> Vec<Lexeme> {
+        let mut results = Vec::with_capacity(lexemes.len());
+        // the parts may also be lexemes of the index mode
+        let mut emitted = HashSet::new();
+        let mut push = |results: &mut Vec<Lexeme>, lexeme: Lexeme| {
+            let key = (
+                lexeme.begin_pos(),
+                lexeme.end_pos(),
+                lexeme.lexeme_text().to_string(),
+            );
+            if emitted.insert(key) {
+                results.push(lexeme);
+            }
+        };
+        for lexeme in lexemes.into_iter() {
+            let parts = match lexeme.lexeme_type() {
+                LexemeType::LETTER | LexemeType::ENGLISH => self.split(&lexeme, normalized),
+                LexemeType::CNWORD
+                    if is_letter_word(utf8_slice(
+                        &normalized.text,
+                        lexeme.begin_pos(),
+                        lexeme.end_pos(),
+                    )) =>
+                {
+                    self.split(&lexeme, normalized)
+                }
+                _ => Vec::new(),
+            };
+            if parts.len() < 2 {
+                push(&mut results, lexeme);
+                continue;
+            }
+            let input = normalized.text.as_str();
+            let (begin, end) = (lexeme.begin_pos(), lexeme.end_pos());
+            if self.preserve_original {
+                push(&mut results, lexeme);
+            }
+            let mut catenated = String::new();
+            for part in parts.iter() {
+                let text = utf8_slice(input, part.start, part.end);
+                let lexeme_type = if text.chars().all(|c| c.is_ascii_digit()) {
+                    LexemeType::ARABIC
+                } else {
+                    LexemeType::ENGLISH
+                };
+                let mut part_lexeme = Lexeme::new(part.clone(), lexeme_type);
+                part_lexeme.set_lexeme_text(text);
+                push(&mut results, part_lexeme);
+                catenated.push_str(text);
+            }
+            if self.catenate_all && catenated != utf8_slice(input, begin, end) {
+                let mut catenated_lexeme = Lexeme::new(begin..end, LexemeType::LETTER);
+                catenated_lexeme.set_lexeme_text(&catenated);
+                push(&mut results, catenated_lexeme);
+            }
+        }
+        results
+    }
+
+    // positions of the parts of the lexeme
+    fn split(&self, lexeme: &Lexeme, normalized: &NormalizedText) -> Vec<Range<usize>> {
+        let begin = lexeme.begin_pos();
+        let chars: Vec<char> = utf8_slice(&normalized.text, begin, lexeme.end_pos())
+            .chars()
+            .collect();
+        let upper: Vec<bool> = (0..chars.len())
+            .map(|i| normalized.was_uppercase(begin + i))
+            .collect();
+        let mut parts = Vec::new();
+        let mut start: Option<usize> = None;
+        for (i, c) in chars.iter().enumerate() {
+            if !c.is_alphanumeric() {
+                if let Some(s) = start.take() {
+                    parts.push(begin + s..begin + i);
+                }
+                continue;
+            }
+            let boundary = match start {
+                Some(s) if i > s => {
+                    let prev = chars[i - 1];
+                    let numeric_change = prev.is_numeric() != c.is_numeric();
+                    // "xM" and the "H" of "XMLHttp"
+                    let case_change = upper[i]
+                        && ((!upper[i - 1] && prev.is_alphabetic())
+                            || (upper[i - 1]
+                                && chars
+                                    .get(i + 1)
+                                    .is_some_and(|n| n.is_alphabetic() && !upper[i + 1])));
+                    (self.split_on_numerics && numeric_change)
+                        || (self.split_on_case_change && case_change && !prev.is_numeric())
+                }
+                _ => false,
+            };
+            if boundary {
+                parts.push(begin + start.unwrap()..begin + i);
+                start = Some(i);
+            } else if start.is_none() {
+                start = Some(i);
+            }
+        }
+        if let Some(s) = start {
+            parts.push(begin + s..begin + chars.len());
+        }
+        parts
+    }
+}
+
+// dictionary words of letters, digits and connectors, like "wi-fi" or "node.js"
+fn is_letter_word(text: &str) -> bool {
+    text.chars()
+        .any(|c| matches!(CharType::from(c), CharType::ENGLISH))
+        && text.chars().all(|c| match CharType::from(c) {
+            CharType::ENGLISH | CharType::ARABIC => true,
+            _ => !c.is_alphanumeric() && !c.is_whitespace(),
+        })
+}
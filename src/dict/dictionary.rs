@@ -1,6 +1,8 @@
 use crate::config::configuration::Configuration;
 use crate::config::default_config::DefaultConfig;
+use crate::core::normalizer::{Normalization, GLOBAL_NORMALIZATION};
 use crate::dict::hit::Hit;
+use crate::dict::importer::DictFormat;
 use crate::dict::trie::Trie;
 use once_cell;
 use once_cell::sync::Lazy;
@@ -23,34 +25,129 @@ pub static GLOBAL_DICT: Lazy<RwLock<Dictionary>> = Lazy::new(|| {
     RwLock::new(dict)
 });
 
-fn load(dict: &mut Trie, file_path: &str) -> bool {
-    let open_file = File::open(file_path);
-    match open_file {
-        Ok(file) => {
-            let reader = BufReader::new(file);
-            for line in reader.lines() {
-                match line {
-                    Ok(word) => {
-                        dict.insert(&word.trim());
-                    }
-                    Err(e) => {
-                        panic!("read dict:{} error:{}", file_path, e);
-                    }
-                }
-            }
-            log::debug!("after load dict:{}, size = {}", file_path, dict.size());
-            return true;
+/// statistics of loading a dict file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadReport {
+    pub file_path: String,
+    /// words inserted into the dict
+    pub accepted: usize,
+    /// words already in the dict, from this file or one loaded before
+    pub duplicate: usize,
+    /// lines of invalid encoding or format, or with control chars
+    pub rejected: usize,
+}
+
+/// statistics of the dict files of ik.yml and of the imported ones, in the
+/// order of loading
+pub fn load_reports() -> Vec<LoadReport> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature="use-parking-lot")] {GLOBAL_DICT.read().load_reports().to_vec()}
+        else /*if #[cfg(feature="use-std-sync")]*/ {
+            GLOBAL_DICT
+                .read()
+                .map_or(Vec::new(), |dict| dict.load_reports().to_vec())
+        }
+    }
+}
+
+fn load(dict: &mut Trie, file_path: &str, normalization: &Normalization) -> LoadReport {
+    let file =
+        File::open(file_path).unwrap_or_else(|e| panic!("open dict:{} error:{}", file_path, e));
+    let lines = BufReader::new(file).split(b'\n').map(|line| {
+        let line = line.unwrap_or_else(|e| panic!("read dict:{} error:{}", file_path, e));
+        String::from_utf8(line).ok()
+    });
+    insert_lines(dict, file_path, lines, DictFormat::IK, normalization)
+}
+
+// insert the words of the lines in the format, None for a line of invalid
+// utf-8
+fn insert_lines(
+    dict: &mut Trie,
+    file_path: &str,
+    lines: impl Iterator<Item = Option<String>>,
+    format: DictFormat,
+    normalization: &Normalization,
+) -> LoadReport {
+    let mut report = LoadReport {
+        file_path: file_path.to_string(),
+        ..Default::default()
+    };
+    let mut changed = Vec::new();
+    for (line_no, line) in lines.enumerate() {
+        let Some(line) = line else {
+            log::warn!("dict:{} line:{} invalid utf-8", file_path, line_no + 1);
+            report.rejected += 1;
+            continue;
+        };
+        // the BOM of files saved by windows editors
+        let line = line.trim_start_matches('\u{FEFF}').trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
         }
-        Err(e) => {
-            panic!("open dict:{} error:{}", file_path, e);
+        let words = format
+            .words(line)
+            .filter(|_| !line.chars().any(is_invalid_char));
+        let Some(words) = words else {
+            log::warn!(
+                "dict:{} line:{} invalid line:{:?}",
+                file_path,
+                line_no + 1,
+                line
+            );
+            report.rejected += 1;
+            continue;
+        };
+        for word in words {
+            let normalized = dict_word(word, normalization);
+            if normalized != word {
+                changed.push(format!("{}->{}", word, normalized));
+            }
+            if dict.insert(&normalized) {
+                report.accepted += 1;
+            } else {
+                report.duplicate += 1;
+            }
         }
     }
+    if !changed.is_empty() {
+        log::warn!(
+            "dict:{} {} words changed by normalization: {}",
+            file_path,
+            changed.len(),
+            changed.join(", ")
+        );
+    }
+    log::info!(
+        "after load dict:{}, accepted = {}, duplicate = {}, rejected = {}, size = {}",
+        file_path,
+        report.accepted,
+        report.duplicate,
+        report.rejected,
+        dict.size()
+    );
+    report
 }
+
+fn is_invalid_char(c: char) -> bool {
+    (c.is_control() && !c.is_whitespace()) || c == '\u{FFFD}' || c == '\u{FEFF}'
+}
+
+// the word as it is matched against the normalized text, the words of a
+// phrase separated by a single space
+fn dict_word(word: &str, normalization: &Normalization) -> String {
+    let word = word.split_whitespace().collect::<Vec<_>>().join(" ");
+    normalization.normalize_word(&word)
+}
+
 /// Dictionary Manager
 pub struct Dictionary {
     main_dict: Trie,
     stop_word_dict: Trie,
+    english_stop_word_dict: Trie,
     quantifier_dict: Trie,
+    normalization: Normalization,
+    load_reports: Vec<LoadReport>,
     cfg: Box<dyn Configuration>,
 }
 
@@ -59,33 +156,39 @@ unsafe impl Send for Dictionary {}
 
 impl Dictionary {
     pub fn new() -> Self {
+        let cfg = DefaultConfig::new();
         Dictionary {
             main_dict: Trie::new(),
             stop_word_dict: Trie::new(),
+            english_stop_word_dict: Trie::new(),
             quantifier_dict: Trie::new(),
-            cfg: Box::new(DefaultConfig::new()),
+            normalization: GLOBAL_NORMALIZATION.clone(),
+            load_reports: Vec::new(),
+            cfg: Box::new(cfg),
         }
     }
 
     fn init(&mut self) -> bool {
-        self.load_main_dict() && self.load_stop_word_dict() && self.load_quantifier_dict()
+        self.load_main_dict()
+            && self.load_stop_word_dict()
+            && self.load_english_stop_word_dict()
+            && self.load_quantifier_dict()
     }
 
     #[allow(dead_code)]
     pub fn add_words(&mut self, words: Vec<&str>) -> () {
         for word in words.iter() {
-            self.main_dict.insert(word);
+            self.main_dict.insert(&dict_word(word, &self.normalization));
         }
     }
 
     #[allow(dead_code)]
     pub fn disable_words(&mut self, words: Vec<&str>) -> () {
         for word in words.iter() {
-            self.main_dict.delete(word);
+            self.main_dict.delete(&dict_word(word, &self.normalization));
         }
     }
 
-    #[allow(dead_code)]
     pub fn match_in_main_dict(&self, word: &str) -> Vec<Hit> {
         self.main_dict.match_word(word)
     }
@@ -99,6 +202,15 @@ impl Dictionary {
         self.main_dict.match_word_with_offset(word, offset, length)
     }
 
+    /// words of the main dict with len chars, each accepted at its position
+    pub fn find_in_main_dict(
+        &self,
+        len: usize,
+        accept: &dyn Fn(usize, char) -> bool,
+    ) -> Vec<String> {
+        self.main_dict.find_words(len, accept)
+    }
+
     pub fn match_in_quantifier_dict(&self, word: &str, offset: usize, length: usize) -> Vec<Hit> {
         self.quantifier_dict
             .match_word_with_offset(word, offset, length)
@@ -116,39 +228,128 @@ impl Dictionary {
         false
     }
 
+    /// insert the words of the decoded text of a dict file in the format into
+    /// the main dict
+    pub fn import(&mut self, file_path: &str, text: &str, format: DictFormat) -> LoadReport {
+        let lines = text.lines().map(|line| Some(line.to_string()));
+        let report = insert_lines(
+            &mut self.main_dict,
+            file_path,
+            lines,
+            format,
+            &self.normalization,
+        );
+        self.load_reports.push(report.clone());
+        report
+    }
+
+    /// all the words of the main dict, sorted
+    pub fn main_dict_words(&self) -> Vec<String> {
+        let mut words = self.main_dict.words();
+        words.sort();
+        words
+    }
+
+    /// whether the text normalized by the normalization matches the words of
+    /// the dicts, it has to do all the normalization of the dicts does and
+    /// leave their words the same
+    pub(crate) fn is_normalized_by(&self, normalization: &Normalization) -> bool {
+        if !normalization.covers(&self.normalization) {
+            return false;
+        }
+        let dict_normalization = &self.normalization;
+        if normalization.form == dict_normalization.form
+            && normalization.case_fold == dict_normalization.case_fold
+            && normalization.strip_accents == dict_normalization.strip_accents
+        {
+            return true;
+        }
+        // the simplified words are not converted again
+        let normalization = Normalization {
+            traditional_to_simplified: false,
+            ..normalization.clone()
+        };
+        // lowercase ascii and cjk ideographs are left the same by any form
+        let unchanged = |c: char| {
+            (c.is_ascii() && !c.is_ascii_uppercase()) || ('\u{4E00}'..='\u{9FFF}').contains(&c)
+        };
+        [
+            &self.main_dict,
+            &self.quantifier_dict,
+            &self.stop_word_dict,
+            &self.english_stop_word_dict,
+        ]
+        .iter()
+        .flat_map(|dict| dict.words())
+        .filter(|word| !word.chars().all(unchanged))
+        .all(|word| normalization.normalize_word(&word) == word)
+    }
+
+    /// statistics of the dict files loaded or imported, in the order of
+    /// loading
+    pub fn load_reports(&self) -> &[LoadReport] {
+        &self.load_reports
+    }
+
+    /// whole english word in the english stop word dict
+    pub fn is_english_stop_word(&self, word: &str) -> bool {
+        self.english_stop_word_dict.exist(word)
+    }
+
     fn load_main_dict(&mut self) -> bool {
         let file_path = self.cfg.get_main_dictionary();
-        if load(&mut self.main_dict, file_path.as_str()) {
-            return self.load_ext_dict();
-        }
-        false
+        let report = load(&mut self.main_dict, file_path.as_str(), &self.normalization);
+        self.load_reports.push(report);
+        self.load_ext_dict()
     }
 
     fn load_ext_dict(&mut self) -> bool {
         let ext_dict_files = self.cfg.get_ext_dictionaries();
-        let mut ret = true;
         for ext_dict_file in ext_dict_files.iter() {
-            if !load(&mut self.main_dict, ext_dict_file.as_str()) {
-                ret = false;
-            }
+            let report = load(
+                &mut self.main_dict,
+                ext_dict_file.as_str(),
+                &self.normalization,
+            );
+            self.load_reports.push(report);
         }
-        ret
+        true
     }
 
     fn load_stop_word_dict(&mut self) -> bool {
         let ext_stop_word_dict_files = self.cfg.get_ext_stop_word_dictionaries();
-        let mut ret = true;
         for stop_file in ext_stop_word_dict_files.iter() {
-            if !load(&mut self.stop_word_dict, stop_file.as_str()) {
-                ret = false;
-            }
+            let report = load(
+                &mut self.stop_word_dict,
+                stop_file.as_str(),
+                &self.normalization,
+            );
+            self.load_reports.push(report);
+        }
+        true
+    }
+
+    fn load_english_stop_word_dict(&mut self) -> bool {
+        if let Some(file_path) = self.cfg.get_english_stop_word_dictionary() {
+            let report = load(
+                &mut self.english_stop_word_dict,
+                file_path.as_str(),
+                &self.normalization,
+            );
+            self.load_reports.push(report);
         }
-        ret
+        true
     }
 
     fn load_quantifier_dict(&mut self) -> bool {
         let file_path = self.cfg.get_quantifier_dictionary();
-        load(&mut self.quantifier_dict, file_path.as_str())
+        let report = load(
+            &mut self.quantifier_dict,
+            file_path.as_str(),
+            &self.normalization,
+        );
+        self.load_reports.push(report);
+        true
     }
 }
 
@@ -173,6 +374,56 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_normalized_words() {
+        let mut dictionary = Dictionary::new();
+        dictionary.add_words(vec!["iPhone", "ＧｉｔＨｕｂ", "New  York"]);
+        for word in ["iphone", "github", "new york"] {
+            let hits = dictionary.match_in_main_dict(word);
+            assert!(hits
+                .iter()
+                .any(|hit| hit.is_match() && hit.len() == word.len()));
+        }
+        assert!(!dictionary.match_in_main_dict("iPhone")[0].is_match());
+        dictionary.disable_words(vec!["IPHONE"]);
+        assert!(!dictionary.match_in_main_dict("iphone")[0].is_match());
+    }
+
+    #[test]
+    fn test_load_report() {
+        let file_path = std::env::temp_dir().join("ik_test_load_report.dic");
+        let mut content = "\u{FEFF}中国\n\n# comment\n  银行 \r\n中国\nbad\u{7}word\n"
+            .as_bytes()
+            .to_vec();
+        content.extend_from_slice(b"\xff\xfe\n");
+        std::fs::write(&file_path, content).unwrap();
+        let mut trie = Trie::new();
+        let report = load(
+            &mut trie,
+            file_path.to_str().unwrap(),
+            &Normalization::default(),
+        );
+        std::fs::remove_file(&file_path).unwrap();
+        assert_eq!(
+            (2, 1, 2),
+            (report.accepted, report.duplicate, report.rejected)
+        );
+        assert!(trie.exist("中国"));
+        assert!(trie.exist("银行"));
+        assert!(!trie.exist("# comment"));
+
+        let mut dictionary = Dictionary::new();
+        dictionary.init();
+        // the main dict and the ext dicts
+        let main_files = 1 + dictionary.cfg.get_ext_dictionaries().len();
+        let accepted: usize = dictionary.load_reports()[..main_files]
+            .iter()
+            .map(|report| report.accepted)
+            .sum();
+        assert_eq!(dictionary.main_dict.size(), accepted);
+        assert!(load_reports()[0].file_path.ends_with("main2012.dic"));
+    }
+
     #[test]
     fn test_thread_safe() {
         let dict = Dictionary::new();
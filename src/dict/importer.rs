@@ -0,0 +1,148 @@
+use crate::dict::dictionary::GLOBAL_DICT;
+pub use crate::dict::dictionary::LoadReport;
+use encoding_rs::GB18030;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// formats of dict files to import
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictFormat {
+    /// a word per line, the dicts of ik.yml and of java IK, whose older dicts
+    /// are encoded in GBK or GB18030
+    IK,
+    /// `word [freq] [pos]` lines of the dict.txt of jieba
+    Jieba,
+    /// `traditional simplified [pin1 yin1] /definition/` lines of CC-CEDICT,
+    /// both forms of a word are imported
+    CcCedict,
+}
+
+impl DictFormat {
+    /// the text of the dict file, the java IK dicts not in utf-8 are decoded
+    /// as GB18030, a superset of GBK
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => text.to_string(),
+            Err(_) if *self == DictFormat::IK => GB18030.decode(bytes).0.into_owned(),
+            Err(_) => String::from_utf8_lossy(bytes).into_owned(),
+        }
+    }
+
+    /// the words of a trimmed line, None if the line is not of the format
+    pub fn words<'a>(&self, line: &'a str) -> Option<Vec<&'a str>> {
+        match self {
+            DictFormat::IK => Some(vec![line]),
+            DictFormat::Jieba => {
+                let mut fields = line.split_whitespace();
+                let word = fields.next()?;
+                match fields.next() {
+                    Some(freq) if freq.parse::<u64>().is_err() => None,
+                    _ => Some(vec![word]),
+                }
+            }
+            DictFormat::CcCedict => {
+                let (words, rest) = line.split_once(" [")?;
+                if !rest.contains("] /") {
+                    return None;
+                }
+                let (traditional, simplified) = words.split_once(' ')?;
+                if traditional == simplified {
+                    Some(vec![simplified])
+                } else {
+                    Some(vec![simplified, traditional])
+                }
+            }
+        }
+    }
+}
+
+/// import the words of a dict file in the format into the main dict, they are
+/// normalized like the dicts of ik.yml
+pub fn import_dict(file_path: &str, format: DictFormat) -> io::Result<LoadReport> {
+    let text = format.decode(&std::fs::read(file_path)?);
+    let mut dict = {cfg_if::cfg_if! {
+        if #[cfg(feature="use-parking-lot")] {GLOBAL_DICT.write()}
+        else /*if #[cfg(feature="use-std-sync")]*/ {
+            GLOBAL_DICT
+                .write()
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?
+        }
+    }};
+    Ok(dict.import(file_path, &text, format))
+}
+
+/// write the words of the main dict, including the imported ones, to a file
+/// of the IK format in utf-8, returns the number of words
+pub fn export_dict(file_path: &str) -> io::Result<usize> {
+    let words = {cfg_if::cfg_if! {
+        if #[cfg(feature="use-parking-lot")] {GLOBAL_DICT.read().main_dict_words()}
+        else /*if #[cfg(feature="use-std-sync")]*/ {
+            GLOBAL_DICT
+                .read()
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?
+                .main_dict_words()
+        }
+    }};
+    write_words(file_path, &words)
+}
+
+// write the words to a file, a word per line
+fn write_words(file_path: &str, words: &[String]) -> io::Result<usize> {
+    let mut writer = BufWriter::new(File::create(file_path)?);
+    for word in words.iter() {
+        writeln!(writer, "{}", word)?;
+    }
+    writer.flush()?;
+    Ok(words.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dict::dictionary::Dictionary;
+
+    #[test]
+    fn test_words() {
+        assert_eq!(Some(vec!["云计算"]), DictFormat::IK.words("云计算"));
+        assert_eq!(Some(vec!["云计算"]), DictFormat::Jieba.words("云计算 5 n"));
+        assert_eq!(Some(vec!["创新办"]), DictFormat::Jieba.words("创新办"));
+        assert_eq!(None, DictFormat::Jieba.words("云计算 n"));
+        assert_eq!(
+            Some(vec!["电脑", "電腦"]),
+            DictFormat::CcCedict.words("電腦 电脑 [dian4 nao3] /computer/")
+        );
+        assert_eq!(
+            Some(vec!["中国"]),
+            DictFormat::CcCedict.words("中国 中国 [Zhong1 guo2] /China/")
+        );
+        assert_eq!(None, DictFormat::CcCedict.words("電腦 电脑"));
+    }
+
+    #[test]
+    fn test_decode() {
+        // "中文" in GBK
+        assert_eq!("中文", DictFormat::IK.decode(b"\xd6\xd0\xce\xc4"));
+        assert_eq!("中文", DictFormat::IK.decode("中文".as_bytes()));
+        assert_eq!("\u{FFFD}", DictFormat::Jieba.decode(b"\xd6"));
+    }
+
+    #[test]
+    fn test_import_export() {
+        let dir = std::env::temp_dir();
+        let export_path = dir.join("ik_test_export.dic");
+        let mut dictionary = Dictionary::new();
+        // "宇宙大爆炸" in GBK
+        let text = DictFormat::IK.decode(b"\xd3\xee\xd6\xe6\xb4\xf3\xb1\xac\xd5\xa8\n");
+        let report = dictionary.import("ik_test_import.dic", &text, DictFormat::IK);
+        assert_eq!(
+            (1, 0, 0),
+            (report.accepted, report.duplicate, report.rejected)
+        );
+        let words = dictionary.main_dict_words();
+        let count = write_words(export_path.to_str().unwrap(), &words).unwrap();
+        let exported = std::fs::read_to_string(&export_path).unwrap();
+        std::fs::remove_file(&export_path).unwrap();
+        assert_eq!(1, count);
+        assert_eq!("宇宙大爆炸\n", exported);
+    }
+}
@@ -1,3 +1,6 @@
 pub(crate) mod dictionary;
 pub(crate) mod hit;
+pub mod importer;
 pub mod trie;
+
+pub use dictionary::{load_reports, LoadReport};
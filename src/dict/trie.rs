@@ -91,23 +91,55 @@ impl TrieNode {
 
     pub fn insert(&mut self, string_val: &str) -> usize {
         let mut current_node = self;
-        let char_count = string_val.chars().count();
-        let mut final_state = false;
-        let mut insert_success = 0usize;
-        for (counter, curr_char) in string_val.chars().enumerate() {
+        for curr_char in string_val.chars() {
             if !current_node.child_nodes.contains_key(&curr_char) {
-                if counter == char_count - 1 {
-                    final_state = true;
-                }
-                if current_node.add_child(curr_char, final_state) {
-                    if final_state {
-                        insert_success += 1;
-                    }
-                }
+                current_node.add_child(curr_char, false);
             }
             current_node = current_node.child_nodes.get_mut(&curr_char).unwrap();
         }
-        insert_success
+        // a prefix of a word inserted before is only marked final
+        if current_node.is_root() || current_node.final_state {
+            return 0;
+        }
+        current_node.final_state = true;
+        1
+    }
+
+    // words below the node of depth len, descending only into the chars
+    // accepted at their depth
+    fn collect_words(
+        &self,
+        prefix: &mut String,
+        len: usize,
+        accept: &dyn Fn(usize, char) -> bool,
+        words: &mut Vec<String>,
+    ) {
+        let depth = prefix.chars().count();
+        if depth == len {
+            if self.final_state {
+                words.push(prefix.clone());
+            }
+            return;
+        }
+        for (c, child) in self.child_nodes.iter() {
+            if accept(depth, *c) {
+                prefix.push(*c);
+                child.collect_words(prefix, len, accept, words);
+                prefix.pop();
+            }
+        }
+    }
+
+    // all the words below the node
+    fn collect_all(&self, prefix: &mut String, words: &mut Vec<String>) {
+        if self.final_state {
+            words.push(prefix.clone());
+        }
+        for (c, child) in self.child_nodes.iter() {
+            prefix.push(*c);
+            child.collect_all(prefix, words);
+            prefix.pop();
+        }
     }
 
     pub fn match_with_offset(&self, string_val: &str, offset: usize, length: usize) -> Vec<Hit> {
@@ -116,8 +148,14 @@ impl TrieNode {
         let char_list: Vec<char> = string_val.chars().collect();
         if offset + length <= char_list.len() {
             let mut end = offset;
-            for counter in offset..offset + length {
-                let curr_char = char_list[counter];
+            let mut counter = offset;
+            while counter < offset + length {
+                // a run of whitespace matches the single space of a phrase,
+                // "new  york" matches "new york"
+                let curr_char = match char_list[counter] {
+                    c if c.is_whitespace() => ' ',
+                    c => c,
+                };
                 if !current_node.child_nodes.contains_key(&curr_char) {
                     break;
                 }
@@ -130,7 +168,13 @@ impl TrieNode {
                     hits.push(hit);
                 }
                 current_node = current_node.child_nodes.get(&curr_char).unwrap();
+                if curr_char == ' ' {
+                    while counter + 1 < offset + length && char_list[counter + 1].is_whitespace() {
+                        counter += 1;
+                    }
+                }
                 end = counter;
+                counter += 1;
             }
             if !current_node.is_root() {
                 let mut hit = Hit::new_with_pos(offset..end + 1);
@@ -161,11 +205,12 @@ impl Trie {
         }
     }
 
+    /// false if the word was inserted before
     pub fn insert(&mut self, string_val: &str) -> bool {
         let current_node = &mut self.root;
         let insert_num = current_node.insert(string_val);
         self.size += insert_num;
-        true
+        insert_num > 0
     }
 
     pub fn size(&self) -> usize {
@@ -180,12 +225,28 @@ impl Trie {
         true
     }
 
-    #[allow(dead_code)]
     pub fn exist(&self, string_val: &str) -> bool {
         let current_node = &self.root;
         current_node.exist(string_val)
     }
 
+    /// words of len chars whose every char is accepted at its position
+    pub fn find_words(&self, len: usize, accept: &dyn Fn(usize, char) -> bool) -> Vec<String> {
+        let mut words = Vec::new();
+        if len > 0 {
+            self.root
+                .collect_words(&mut String::new(), len, accept, &mut words);
+        }
+        words
+    }
+
+    /// all the words, in no particular order
+    pub fn words(&self) -> Vec<String> {
+        let mut words = Vec::with_capacity(self.size);
+        self.root.collect_all(&mut String::new(), &mut words);
+        words
+    }
+
     #[allow(dead_code)]
     pub fn match_word(&self, string_val: &str) -> Vec<Hit> {
         let root_node = &self.root;
@@ -220,11 +281,27 @@ mod test {
         trie.insert("blues小站");
 
         assert_eq!(false, trie.exist("Testing"));
+        assert_eq!(true, trie.exist("Back"));
+        assert_eq!(true, trie.exist("Background"));
+        assert_eq!(false, trie.exist("Backgro"));
         assert_eq!(true, trie.exist("Brown"));
         assert_eq!(true, trie.exist("申艳超"));
         assert_eq!(false, trie.exist("申超"));
     }
 
+    #[test]
+    fn trie_insert() {
+        let mut trie = Trie::new();
+        assert!(trie.insert("Background"));
+        assert!(trie.insert("Back"));
+        assert!(!trie.insert("Back"));
+        assert!(!trie.insert(""));
+        assert_eq!(2, trie.size());
+        let mut words = trie.words();
+        words.sort();
+        assert_eq!(vec!["Back", "Background"], words);
+    }
+
     #[test]
     fn trie_search() {
         let mut trie = Trie::new();
@@ -242,6 +319,29 @@ mod test {
         }
     }
 
+    #[test]
+    fn trie_phrase() {
+        let mut trie = Trie::new();
+        trie.insert("new york");
+        let text = "new \t york";
+        let hits = trie.match_word(text);
+        assert_eq!(0..10, hits.last().unwrap().pos());
+        assert!(hits.last().unwrap().is_match());
+        assert!(!trie.match_word("newyork").last().unwrap().is_match());
+    }
+
+    #[test]
+    fn trie_supplementary_chars() {
+        let mut trie = Trie::new();
+        trie.insert("𠀀");
+        trie.insert("𠀀𡃁");
+        assert_eq!(true, trie.exist("𠀀𡃁"));
+        assert_eq!(true, trie.exist("𠀀"));
+        assert_eq!(false, trie.exist("𡃁"));
+        let hits = trie.match_word("𠀀𡃁");
+        assert!(hits.iter().any(|hit| hit.is_match() && hit.pos() == (0..2)));
+    }
+
     #[test]
     fn test_thread_safe() {
         let trie = Trie::new();
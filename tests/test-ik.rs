@@ -86,6 +86,34 @@ mod test {
             vec!["中国", "有", "960万平方公里", "的", "国土"],
         );
     }
+    #[test]
+    fn test_adversarial_segment() {
+        let lock_guard = {cfg_if::cfg_if!{
+            if #[cfg(feature="use-parking-lot")] {GLOBAL_IK.read()}
+            else /*#cfg(feature="use-std-sync")*/ {GLOBAL_IK.read().unwrap()}
+        }};
+        let explanation = lock_guard.explain("哈哈哈", TokenMode::SEARCH);
+        assert!(explanation.cross_paths[0].candidates.len() > 1);
+        // a cross path longer than DEFAULT_MAX_CROSS_LEN, and one with more
+        // candidate paths than the budget, are segmented by greedy longest
+        // match alone
+        let budget = IKSegmenter::builder().max_candidate_paths(16).build();
+        for (ik, count) in [(&*lock_guard, 100), (&budget, 40)] {
+            let text = "哈".repeat(count) + "中华人民共和国";
+            let explanation = ik.explain(&text, TokenMode::SEARCH);
+            let cross_path = &explanation.cross_paths[0];
+            assert_eq!(count, cross_path.lexemes.last().unwrap().end_pos());
+            assert_eq!(1, cross_path.candidates.len());
+            let token_texts: Vec<&str> = explanation
+                .tokens
+                .iter()
+                .map(|t| t.lexeme_text())
+                .collect();
+            assert_eq!(text, token_texts.concat());
+            assert_eq!(Some(&"中华人民共和国"), token_texts.last());
+        }
+    }
+
     // SEARCH Mode
     fn assert_search_token(text: &str, expect: Vec<&str>) {
         let lock_guard = {cfg_if::cfg_if!{